@@ -0,0 +1,168 @@
+// Synthetic puzzle inputs, shaped and sized like the real ones,
+// for benchmarking when no cached input is available
+
+use std::fmt::Write;
+
+// xorshift, so generated inputs are the same on every run
+struct Rng(u64);
+
+impl Rng {
+	fn next(&mut self) -> u64 {
+		self.0 ^= self.0 << 13;
+		self.0 ^= self.0 >> 7;
+		self.0 ^= self.0 << 17;
+		self.0
+	}
+	fn below(&mut self, n:u64) -> u64 {
+		self.next() % n
+	}
+	fn pick(&mut self, chars:&str) -> char {
+		let i = self.below(chars.len() as u64) as usize;
+		chars.as_bytes()[i] as char
+	}
+}
+
+pub fn input(day:u8) -> String {
+	let mut rng = Rng(0x2023_1201 + u64::from(day));
+	let rng = &mut rng;
+	match day {
+		1 => day01(rng),
+		2 => day02(rng),
+		3 => day03(rng),
+		4 => day04(rng),
+		5 => day05(rng),
+		6 => day06(rng),
+		7 => day07(rng),
+		8 => day08(rng),
+		_ => panic!("No input generator for day {day}")
+	}
+}
+
+fn day01(rng:&mut Rng) -> String {
+	(0..1000).map(|_| {
+		let len = 5 + rng.below(35);
+		let mut line:String = (0..len).map(|_| rng.pick("abcdefghijklmnopqrstuvwxyz123456789")).collect();
+		// there must be at least one digit
+		line.push(rng.pick("123456789"));
+		line + "\n"
+	})
+	.collect()
+}
+
+fn day02(rng:&mut Rng) -> String {
+	(1..=100).map(|id| {
+		let hands = (0..3 + rng.below(4)).map(|_| {
+			let mut hand = vec![];
+			for color in ["red","green","blue"] {
+				if rng.below(3) > 0 {
+					hand.push(format!("{} {color}", 1 + rng.below(20)));
+				}
+			}
+			hand
+		})
+		.filter(|hand| !hand.is_empty())
+		.map(|hand| hand.join(", "))
+		.collect::<Vec<_>>();
+		format!("Game {id}: {}\n", hands.join("; "))
+	})
+	.collect()
+}
+
+fn day03(rng:&mut Rng) -> String {
+	const SIZE:usize = 140;
+	(0..SIZE).map(|_| {
+		let mut row = String::new();
+		while row.len() < SIZE {
+			match rng.below(100) {
+				0..=7 => {
+					// a part number, always followed by a separator
+					let digits = 1 + rng.below(3);
+					(0..digits).for_each(|_| row.push(rng.pick("0123456789")));
+					row.push('.');
+				}
+				8..=11 => row.push(rng.pick("*#+$/@=%&-")),
+				_ => row.push('.'),
+			}
+		}
+		row.truncate(SIZE);
+		row + "\n"
+	})
+	.collect()
+}
+
+fn day04(rng:&mut Rng) -> String {
+	(1..=200).map(|id| {
+		let mut numbers = |n| (0..n).fold(String::new(), |mut s, _| {
+			write!(s, " {:2}", 1 + rng.below(99)).unwrap();
+			s
+		});
+		let winners = numbers(10);
+		let draw = numbers(25);
+		format!("Card {id:3}:{winners} |{draw}\n")
+	})
+	.collect()
+}
+
+fn day05(rng:&mut Rng) -> String {
+	const STEPS:[&str;8] = ["seed","soil","fertilizer","water","light","temperature","humidity","location"];
+	const MAX:u64 = 1 << 32;
+
+	let seeds:Vec<String> = (0..20).map(|_| rng.below(MAX).to_string()).collect();
+	let mut input = format!("seeds: {}\n", seeds.join(" "));
+
+	STEPS.windows(2).for_each(|w| {
+		write!(input, "\n{}-to-{} map:\n", w[0], w[1]).unwrap();
+		(0..20 + rng.below(20)).for_each(|_| {
+			let len = 1 + rng.below(MAX >> 6);
+			let dst = rng.below(MAX - len);
+			let src = rng.below(MAX - len);
+			writeln!(input, "{dst} {src} {len}").unwrap();
+		});
+	});
+	input
+}
+
+fn day06(rng:&mut Rng) -> String {
+	let races:Vec<(u64,u64)> = (0..4).map(|_| {
+		let time = 40 + rng.below(60);
+		let dist = rng.below(time * time / 4);
+		(time,dist)
+	})
+	.collect();
+	let row = |values:Vec<u64>| values.iter().map(|v| format!("{v:5}")).collect::<String>();
+	format!(
+		"Time:    {}\nDistance:{}\n",
+		row(races.iter().map(|r| r.0).collect()),
+		row(races.iter().map(|r| r.1).collect()),
+	)
+}
+
+fn day07(rng:&mut Rng) -> String {
+	(0..1000).map(|_| {
+		let cards:String = (0..5).map(|_| rng.pick("AKQJT98765432")).collect();
+		format!("{cards} {}\n", 1 + rng.below(1000))
+	})
+	.collect()
+}
+
+fn day08(rng:&mut Rng) -> String {
+	const NODES:u32 = 700;
+
+	// distinct ids, other than the start and end ones
+	let id = |n:u32| -> String {
+		(0..3).rev().map(|e| char::from(b'A' + u8::try_from(n / 26_u32.pow(e) % 26).unwrap())).collect()
+	};
+	let path:Vec<String> =
+		std::iter::once("AAA".to_string())
+		.chain((1..NODES).map(id))
+		.chain(std::iter::once("ZZZ".to_string()))
+		.collect();
+
+	let directions:String = (0..270).map(|_| rng.pick("LR")).collect();
+	let mut input = format!("{directions}\n\n");
+
+	// a single chain leading from AAA to ZZZ
+	path.windows(2).for_each(|w| writeln!(input, "{} = ({}, {})", w[0], w[1], w[1]).unwrap());
+	writeln!(input, "ZZZ = (ZZZ, ZZZ)").unwrap();
+	input
+}
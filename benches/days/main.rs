@@ -0,0 +1,59 @@
+use std::{env, hint::black_box, io, path::PathBuf};
+use criterion::{criterion_group, criterion_main, Criterion};
+
+use advent_of_code_2023::{config::{self, Config, Settings}, days::*, input};
+
+mod generate;
+
+// the configured inputs directory, like `aoc` has it, but always for the year of the solvers
+fn configure() {
+	let file = env::var_os("AOC_CONFIG").map_or(PathBuf::from(config::FILE), PathBuf::from);
+	let profile = env::var("AOC_PROFILE").ok();
+	let settings = Settings { year: Some(YEAR), ..Settings::default() };
+	config::init(Config::load(&file, profile.as_deref(), settings).unwrap_or_else(|e| panic!("can't configure: {e}")));
+}
+
+// use the cached puzzle input if there is one, decrypting it if needed, otherwise a generated one
+fn input(day:u8) -> String {
+	let path = input::path(day);
+	match input::cached(day) {
+		Ok(input) => {
+			eprintln!("day{day:02}: the input in {}", path.display());
+			input
+		}
+		Err(e) if e.kind() == io::ErrorKind::NotFound => {
+			eprintln!("day{day:02}: a generated input, as there's no {} (or no key for its encrypted version)", path.display());
+			generate::input(day)
+		}
+		Err(e) => panic!("can't read {}: {e}", path.display()),
+	}
+}
+
+// bench "dayNN/parse" and "dayNN/partN" separately, so solving excludes parsing
+macro_rules! bench_day {
+	($c:expr, $day:ident, $n:literal, [$($part:ident),+]) => {{
+		let input = input($n);
+		let mut group = $c.benchmark_group(stringify!($day));
+		group.bench_function("parse", |b| b.iter(|| $day::parse(black_box(&input))));
+		let parsed = $day::parse(&input);
+		$(
+			group.bench_function(stringify!($part), |b| b.iter(|| $day::$part(black_box(&parsed))));
+		)+
+		group.finish();
+	}};
+}
+
+fn days(c: &mut Criterion) {
+	configure();
+	bench_day!(c, day01, 1, [part1]);
+	bench_day!(c, day02, 2, [part1]);
+	bench_day!(c, day03, 3, [part1]);
+	bench_day!(c, day04, 4, [part1]);
+	bench_day!(c, day05, 5, [part1]);
+	bench_day!(c, day06, 6, [part1]);
+	bench_day!(c, day07, 7, [part1]);
+	bench_day!(c, day08, 8, [part1]);
+}
+
+criterion_group!(benches, days);
+criterion_main!(benches);
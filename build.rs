@@ -0,0 +1,80 @@
+use std::{env, fs, path::Path};
+
+fn main() {
+	println!("cargo::rerun-if-changed=build.rs");
+
+	fixtures();
+
+	#[cfg(feature = "embed")]
+	embed();
+
+	// regenerate the C header, which is kept in the repository
+	#[cfg(feature = "c-header")]
+	{
+		println!("cargo::rerun-if-changed=src/ffi.rs");
+		println!("cargo::rerun-if-changed=cbindgen.toml");
+		let dir = std::env::var("CARGO_MANIFEST_DIR").unwrap();
+		cbindgen::generate(&dir)
+			.expect("Unable to generate the C header")
+			.write_to_file(std::path::Path::new(&dir).join("include").join("aoc.h"));
+	}
+}
+
+/// A test for every example under examples/2023, see `days::fixtures`
+fn fixtures() {
+	println!("cargo::rerun-if-changed=examples/2023");
+
+	let dir = Path::new(&env::var("CARGO_MANIFEST_DIR").unwrap()).join("examples").join("2023");
+	let mut tests = String::new();
+	let mut days:Vec<_> = fs::read_dir(&dir).into_iter().flatten().flatten().map(|e| e.path()).collect();
+	days.sort();
+	for day_dir in days {
+		let Some(day) = day_dir.file_name().and_then(|n| n.to_str()?.strip_prefix("day")?.parse::<u8>().ok()) else {
+			continue;
+		};
+		println!("cargo::rerun-if-changed={}", day_dir.display());
+		let mut examples:Vec<_> = fs::read_dir(&day_dir).unwrap().flatten().map(|e| e.path()).collect();
+		examples.sort();
+		for example in examples {
+			// named part<N>-<K>.txt, with the answer in part<N>-<K>.answer
+			let Some(name) = example.file_name().and_then(|n| n.to_str()?.strip_suffix(".txt")) else {
+				continue;
+			};
+			let Some((part, k)) = name.strip_prefix("part").and_then(|n| n.split_once('-')) else {
+				continue;
+			};
+			let Ok(part) = part.parse::<u8>() else {
+				continue;
+			};
+			let k:String = k.chars().map(|c| if c.is_ascii_alphanumeric() { c } else { '_' }).collect();
+			tests += &format!("#[test]\nfn day{day:02}_part{part}_{k}() {{ check({day}, {part}, \"day{day:02}/{name}\") }}\n");
+		}
+	}
+	fs::write(Path::new(&env::var("OUT_DIR").unwrap()).join("fixtures.rs"), tests).unwrap();
+}
+
+#[cfg(feature = "embed")]
+#[path = "src/embedded/files.rs"]
+mod files;
+
+/// The cached inputs and the ledger, from where the configuration and the AOC_* variables put them,
+/// to be built into the binary, see `embedded`
+#[cfg(feature = "embed")]
+fn embed() {
+	let root = Path::new(&env::var("CARGO_MANIFEST_DIR").unwrap()).to_owned();
+	for var in ["AOC_CONFIG", "AOC_PROFILE", "AOC_YEAR", "AOC_INPUTS", "AOC_CACHE"] {
+		println!("cargo::rerun-if-env-changed={var}");
+	}
+
+	let config = root.join(env::var("AOC_CONFIG").unwrap_or_else(|_| "aoc.toml".into()));
+	println!("cargo::rerun-if-changed={}", config.display());
+	let text = fs::read_to_string(&config).ok();
+	let dirs = files::dirs(text.as_deref(), |var| env::var(var).ok(), 2023)
+		.unwrap_or_else(|e| panic!("{}: {e}", config.display()));
+	dirs.iter().for_each(|dir| println!("cargo::rerun-if-changed={}", root.join(dir).display()));
+
+	let entries:String = files::select(&root, &dirs).iter()
+		.map(|file| format!("\t({:?}, include_bytes!({:?})),\n", file.display().to_string(), root.join(file).display().to_string()))
+		.collect();
+	fs::write(Path::new(&env::var("OUT_DIR").unwrap()).join("embedded.rs"), format!("&[\n{entries}]\n")).unwrap();
+}
@@ -0,0 +1,61 @@
+#ifndef AOC_H
+#define AOC_H
+
+/* Generated by cbindgen from src/ffi.rs (cargo build --features c-header), don't edit */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+/**
+ * What aoc_solve did. Panics never cross the boundary, they are reported as one of these.
+ */
+typedef enum AocStatus {
+  AOC_STATUS_OK = 0,
+  AOC_STATUS_NULL_POINTER = 1,
+  AOC_STATUS_INVALID_UTF8 = 2,
+  AOC_STATUS_NO_SOLVER = 3,
+  AOC_STATUS_SOLVER_PANICKED = 4,
+  AOC_STATUS_BUFFER_TOO_SMALL = 5,
+} AocStatus;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+/**
+ * Solve a puzzle part's input, writing the answer as a NUL terminated string to `out`.
+ *
+ * On success, and when `out` is too small, `out_len` (unless NULL) is set to the answer's length,
+ * not counting the NUL, so a buffer of `out_len + 1` bytes will do.
+ * A solver's panic isn't printed; aoc_status_message gives its message instead.
+ *
+ * # Safety
+ *
+ * `input` must point to `input_len` readable bytes, and `out` to `out_cap` writable ones.
+ */
+enum AocStatus aoc_solve(uint32_t year,
+                         uint32_t day,
+                         uint32_t part,
+                         const uint8_t *input,
+                         size_t input_len,
+                         char *out,
+                         size_t out_cap,
+                         size_t *out_len);
+
+/**
+ * A description of a status code, "unknown status" for codes that aren't an AocStatus.
+ *
+ * For AOC_STATUS_SOLVER_PANICKED, it's what the solver panicked with, if the last aoc_solve
+ * on this thread was the one that panicked; that string lasts until the next aoc_solve on this
+ * thread. The other descriptions are static.
+ */
+const char *aoc_status_message(uint32_t status);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* AOC_H */
@@ -0,0 +1,34 @@
+// Solve a puzzle through the C interface:
+//   cargo build --release
+//   cc include/examples/solve.c -Iinclude -Ltarget/release -ladvent_of_code_2023 -o solve
+//   LD_LIBRARY_PATH=target/release ./solve 7 1 < inputs/2023/7.txt
+
+#include <stdio.h>
+#include <stdlib.h>
+#include "aoc.h"
+
+int main(int argc, char **argv) {
+	if (argc != 3) {
+		fprintf(stderr, "usage: %s <day> <part> < input\n", argv[0]);
+		return 2;
+	}
+
+	size_t cap = 1 << 16, len = 0, n;
+	uint8_t *input = malloc(cap);
+	while ((n = fread(input + len, 1, cap - len, stdin)) > 0) {
+		len += n;
+		if (len == cap) input = realloc(input, cap *= 2);
+	}
+
+	char answer[64];
+	size_t answer_len;
+	AocStatus status = aoc_solve(2023, atoi(argv[1]), atoi(argv[2]), input, len, answer, sizeof answer, &answer_len);
+	free(input);
+
+	if (status != AOC_STATUS_OK) {
+		fprintf(stderr, "error: %s\n", aoc_status_message(status));
+		return 1;
+	}
+	printf("%s\n", answer);
+	return 0;
+}
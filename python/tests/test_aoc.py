@@ -0,0 +1,90 @@
+"""Tests of the Python bindings, against the extension cargo built.
+
+    cargo build --features python
+    python3 -m unittest discover python/tests
+"""
+
+import importlib.machinery
+import importlib.util
+import os
+import sys
+import unittest
+from pathlib import Path
+
+ROOT = Path(__file__).resolve().parents[2]
+
+
+def load():
+    # the extension as built, unless AOC_LIB points elsewhere
+    path = os.environ.get("AOC_LIB") or next(
+        str(p)
+        for p in [ROOT / "target" / profile / name for profile in ("debug", "release")
+                  for name in ("libadvent_of_code_2023.so", "libadvent_of_code_2023.dylib")]
+        if p.exists()
+    )
+    loader = importlib.machinery.ExtensionFileLoader("aoc", path)
+    spec = importlib.util.spec_from_loader("aoc", loader)
+    module = importlib.util.module_from_spec(spec)
+    sys.modules["aoc"] = module
+    loader.exec_module(module)
+    return module
+
+
+aoc = load()
+
+
+class TestSolvers(unittest.TestCase):
+
+    def test_solve(self):
+        self.assertEqual(aoc.solve(1, 1, "1abc2\npqr3stu8vwx\n"), "50")
+        self.assertEqual(aoc.day01.solve("a1b2c3d4e5f\n"), "15")
+        self.assertIn((8, 1), [(s.day, s.part) for s in aoc.solvers])
+
+    def test_errors(self):
+        with self.assertRaises(ValueError):
+            aoc.solve(25, 2, "")
+        with self.assertRaises(ValueError):
+            aoc.day01.solve("no digits\n")
+
+
+class TestModels(unittest.TestCase):
+
+    def test_games(self):
+        from aoc.day02 import Game
+        game = Game("Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green")
+        self.assertEqual(game.id, 1)
+        self.assertEqual(game.hands, [(4, 0, 3), (1, 2, 6), (0, 2, 0)])
+        self.assertEqual(len(aoc.day02.parse("Game 1: 1 red\nGame 2: 2 blue\n")), 2)
+
+    def test_maps(self):
+        from aoc.day05 import Map
+        m = Map("seed-to-soil map:\n50 98 2\n52 50 48\n")
+        self.assertEqual((m.src, m.dst), ("seed", "soil"))
+        self.assertEqual([m.map(v) for v in (0, 50, 97, 98)], [0, 52, 99, 50])
+        seeds, maps = aoc.day05.parse("seeds: 79 14\n\nseed-to-soil map:\n50 98 2\n")
+        self.assertEqual(seeds, [79, 14])
+        self.assertEqual(len(maps), 1)
+
+    def test_hands(self):
+        from aoc.day07 import Hand
+        hands = aoc.day07.parse("32T3K 765\nT55J5 684\nKK677 28\nKTJJT 220\nQQQJA 483\n")
+        self.assertEqual([h.bid for h in sorted(hands)], [765, 220, 28, 684, 483])
+        self.assertEqual(Hand("QQQJA 483").category, "ThreeOfAKind")
+        self.assertGreater(Hand("AAAAA 1"), Hand("KKKKK 1"))
+        with self.assertRaises(ValueError):
+            Hand("not a hand")
+
+    def test_network(self):
+        from aoc.day08 import Network
+        network = Network("LLR\n\nAAA = (BBB, BBB)\nBBB = (AAA, ZZZ)\nZZZ = (ZZZ, ZZZ)\n")
+        self.assertEqual(network.directions, "LLR")
+        self.assertEqual(network.nodes["BBB"], ("AAA", "ZZZ"))
+        self.assertEqual(network.steps(), "6")
+
+        # ZZZ is only ever gone to, so it has no left and right
+        with self.assertRaises(ValueError):
+            Network("L\n\nAAA = (ZZZ, ZZZ)\n")
+
+
+if __name__ == "__main__":
+    unittest.main()
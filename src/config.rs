@@ -0,0 +1,268 @@
+// Settings, from aoc.toml, then the environment, then the command line
+
+use std::{collections::BTreeMap, env, fs, io, path::{Path, PathBuf}, sync::OnceLock, time::Duration};
+use clap::ValueEnum;
+use serde::Deserialize;
+
+use crate::{days::YEAR, output::Format};
+
+pub const FILE:&str = "aoc.toml";
+
+/// When answers get submitted
+#[derive(ValueEnum,Deserialize,Debug,Clone,Copy,Default,PartialEq,Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum Submit {
+	/// Whenever a part is solved
+	#[default]
+	Always,
+	/// After asking for confirmation, on the command line
+	Ask,
+	/// Never, like --dry-run
+	Never,
+}
+
+/// One layer of settings, where anything left out falls through to the layer below
+#[derive(Deserialize,Debug,Clone,Default,PartialEq)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+pub struct Settings {
+	pub year: Option<u16>,
+	pub inputs: Option<PathBuf>,
+	pub cache: Option<PathBuf>,
+	pub submit: Option<Submit>,
+	/// For requests to AoC, in seconds
+	pub timeout: Option<u64>,
+	pub format: Option<Format>,
+	/// For `aoc serve`
+	pub port: Option<u16>,
+	/// For `aoc report`
+	pub readme: Option<PathBuf>,
+}
+
+impl Settings {
+
+	/// These settings, with the ones given on top
+	pub fn or(self, over:Settings) -> Settings {
+		Settings {
+			year: over.year.or(self.year),
+			inputs: over.inputs.or(self.inputs),
+			cache: over.cache.or(self.cache),
+			submit: over.submit.or(self.submit),
+			timeout: over.timeout.or(self.timeout),
+			format: over.format.or(self.format),
+			port: over.port.or(self.port),
+			readme: over.readme.or(self.readme),
+		}
+	}
+
+	/// Settings from AOC_* variables, looked up with `var`
+	pub fn from_env(var:impl Fn(&str) -> Option<String>) -> io::Result<Settings> {
+		fn parse<T:std::str::FromStr>(name:&str, value:String) -> io::Result<T> {
+			value.parse().map_err(|_| invalid(format!("{name}={value} is not valid")))
+		}
+		fn value_enum<T:ValueEnum>(name:&str, value:String) -> io::Result<T> {
+			T::from_str(&value, true).map_err(|_| invalid(format!("{name}={value} is not valid")))
+		}
+		Ok(Settings {
+			year: var("AOC_YEAR").map(|v| parse("AOC_YEAR", v)).transpose()?,
+			inputs: var("AOC_INPUTS").map(PathBuf::from),
+			cache: var("AOC_CACHE").map(PathBuf::from),
+			submit: var("AOC_SUBMIT").map(|v| value_enum("AOC_SUBMIT", v)).transpose()?,
+			timeout: var("AOC_TIMEOUT").map(|v| parse("AOC_TIMEOUT", v)).transpose()?,
+			format: var("AOC_FORMAT").map(|v| value_enum("AOC_FORMAT", v)).transpose()?,
+			port: var("AOC_PORT").map(|v| parse("AOC_PORT", v)).transpose()?,
+			readme: var("AOC_README").map(PathBuf::from),
+		})
+	}
+}
+
+/// The contents of aoc.toml: settings, and named profiles of settings to put on top of them
+#[derive(Debug,Default)]
+pub struct File {
+	/// The profile to use, unless another one is asked for
+	pub profile: Option<String>,
+	pub profiles: BTreeMap<String,Settings>,
+	pub settings: Settings,
+}
+
+impl File {
+
+	/// Unknown keys are errors, not ignored, so that misspelt settings don't go unnoticed
+	pub fn parse(text:&str) -> io::Result<File> {
+		// serde can't deny unknown fields next to flattened ones, so the settings are what's left
+		let mut table:toml::Table = toml::from_str(text).map_err(|e:toml::de::Error| invalid(e.to_string()))?;
+		let value = |e:toml::de::Error| invalid(e.to_string());
+		let profile = table.remove("profile").map(|v| v.try_into()).transpose().map_err(value)?;
+		let profiles = table.remove("profiles").map(|v| v.try_into()).transpose().map_err(value)?.unwrap_or_default();
+		let settings = table.try_into().map_err(value)?;
+		Ok(File { profile, profiles, settings })
+	}
+
+	/// The file at `path`, or an empty one when it doesn't exist
+	pub fn load(path:&Path) -> io::Result<File> {
+		match fs::read_to_string(path) {
+			Ok(text) => File::parse(&text).map_err(|e| invalid(format!("{}: {e}", path.display()))),
+			Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(File::default()),
+			Err(e) => Err(e),
+		}
+	}
+
+	/// Its settings, with those of the profile on top
+	pub fn settings(mut self, profile:Option<&str>) -> io::Result<Settings> {
+		match profile.or(self.profile.as_deref()) {
+			None => Ok(self.settings),
+			Some(name) => {
+				let profile = self.profiles.remove(name).ok_or_else(|| invalid(format!("there is no profile {name}")))?;
+				Ok(self.settings.or(profile))
+			}
+		}
+	}
+}
+
+#[derive(Debug,Clone,PartialEq)]
+pub struct Config {
+	/// The AoC event that inputs, answers and records are for
+	pub year: u16,
+	pub inputs: PathBuf,
+	pub cache: PathBuf,
+	pub submit: Submit,
+	pub timeout: Duration,
+	pub format: Format,
+	pub port: u16,
+	pub readme: PathBuf,
+}
+
+impl Default for Config {
+	fn default() -> Self {
+		Config::from(Settings::default())
+	}
+}
+
+impl From<Settings> for Config {
+	fn from(settings:Settings) -> Self {
+		Config {
+			year: settings.year.unwrap_or(YEAR),
+			inputs: settings.inputs.unwrap_or_else(|| "inputs".into()),
+			cache: settings.cache.unwrap_or_else(|| "cache".into()),
+			submit: settings.submit.unwrap_or_default(),
+			timeout: Duration::from_secs(settings.timeout.unwrap_or(30)),
+			format: settings.format.unwrap_or_default(),
+			port: settings.port.unwrap_or(8023),
+			readme: settings.readme.unwrap_or_else(|| "README.md".into()),
+		}
+	}
+}
+
+impl Config {
+
+	/// The configuration file, with the profile asked for, then the environment, then `cli`
+	pub fn load(path:&Path, profile:Option<&str>, cli:Settings) -> io::Result<Config> {
+		let env = Settings::from_env(|name| env::var(name).ok())?;
+		Config::layered(File::load(path)?, profile, env, cli)
+	}
+
+	// each layer on top of the ones before it
+	fn layered(file:File, profile:Option<&str>, env:Settings, cli:Settings) -> io::Result<Config> {
+		Ok(Config::from(file.settings(profile)?.or(env).or(cli)))
+	}
+
+	/// Where the inputs of this year are kept
+	pub fn inputs_dir(&self) -> PathBuf {
+		self.inputs.join(self.year.to_string())
+	}
+
+	/// Where the records of this year are kept
+	pub fn cache_dir(&self) -> PathBuf {
+		self.cache.join(self.year.to_string())
+	}
+
+	/// An HTTP client for AoC
+	pub fn agent(&self) -> ureq::Agent {
+		ureq::AgentBuilder::new()
+			.timeout(self.timeout)
+			.user_agent("github.com/turbohz/advent-of-code-2023")
+			.build()
+	}
+}
+
+static CONFIG:OnceLock<Config> = OnceLock::new();
+
+/// Set the configuration, once, before anything reads it
+pub fn init(config:Config) {
+	if CONFIG.set(config).is_err() {
+		panic!("the configuration was already set");
+	}
+}
+
+/// The configuration, or the default one when none was set
+pub fn get() -> &'static Config {
+	CONFIG.get_or_init(Config::default)
+}
+
+fn invalid(message:String) -> io::Error {
+	io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+#[cfg(test)]
+mod tests {
+	use pretty_assertions::assert_eq;
+	use super::*;
+
+	#[test]
+	fn test_layers() {
+		let file = File::parse(r#"
+			profile = "ci"
+			cache = "records"
+			format = "json"
+
+			[profiles.ci]
+			submit = "never"
+			timeout = 5
+
+			[profiles.slow]
+			timeout = 120
+		"#).unwrap();
+
+		let env = Settings::from_env(|name| (name == "AOC_FORMAT").then(|| "CSV".into())).unwrap();
+		let cli = Settings { year: Some(2022), ..Settings::default() };
+		let config = Config::from(file.settings(None).unwrap().or(env).or(cli));
+
+		assert_eq!(config, Config {
+			year: 2022,
+			inputs: "inputs".into(),
+			cache: "records".into(),
+			submit: Submit::Never,
+			timeout: Duration::from_secs(5),
+			format: Format::Csv,
+			port: 8023,
+			readme: "README.md".into(),
+		});
+		assert_eq!(config.cache_dir(), Path::new("records/2022"));
+	}
+
+	#[test]
+	fn test_precedence() {
+		let file = || File::parse("timeout = 1\n[profiles.ci]\ntimeout = 2\n").unwrap();
+		let env = Settings::from_env(|name| (name == "AOC_TIMEOUT").then(|| "3".into())).unwrap();
+		let cli = Settings { timeout: Some(4), ..Settings::default() };
+		let timeout = |profile, env:&Settings, cli:&Settings| {
+			Config::layered(file(), profile, env.clone(), cli.clone()).unwrap().timeout.as_secs()
+		};
+		let none = Settings::default();
+		assert_eq!(timeout(Some("ci"), &env, &cli), 4);
+		assert_eq!(timeout(Some("ci"), &env, &none), 3);
+		assert_eq!(timeout(Some("ci"), &none, &none), 2);
+		assert_eq!(timeout(None, &none, &none), 1);
+		assert_eq!(timeout(None, &none, &cli), 4);
+	}
+
+	#[test]
+	fn test_invalid() {
+		let file = File::parse("[profiles.ci]\nsubmit = \"never\"\n").unwrap();
+		assert!(file.settings(Some("release")).is_err());
+		assert!(File::parse("submit = \"sometimes\"\n").is_err());
+		// misspelt keys, outside of profiles and in them
+		assert!(File::parse("submits = \"never\"\n").is_err());
+		assert!(File::parse("[profiles.ci]\ntimout = 5\n").is_err());
+		assert!(Settings::from_env(|name| (name == "AOC_YEAR").then(|| "next".into())).is_err());
+	}
+}
@@ -0,0 +1,280 @@
+// Terminal dashboard of the whole calendar
+
+use std::{collections::HashMap, env, io, panic, process::{Command, Stdio}, sync::{mpsc, Arc}, thread, time::Duration};
+use ratatui::{
+	crossterm::event::{self, Event, KeyCode, KeyEventKind},
+	layout::{Constraint, Layout},
+	style::{Modifier, Style, Stylize},
+	text::Line,
+	widgets::{Block, Row as TableRow, Table, TableState},
+	DefaultTerminal, Frame,
+};
+
+use crate::{config::{self, Submit}, days::{self, SOLVERS}, ledger::Ledger, runner::{self, Run}, timings::Timings};
+
+const DAYS:u8 = 25;
+
+// worker threads are named so their panics don't mess the screen
+const WORKER:&str = "dashboard-worker";
+
+#[derive(Debug,Clone,Copy,PartialEq,Eq)]
+pub enum Status {
+	Running,
+	Passed,
+	Failed,
+}
+
+/// What the dashboard shows about a day
+#[derive(Debug,Clone,PartialEq,Eq)]
+pub struct Row {
+	pub day: u8,
+	pub stars: usize,
+	/// whether parts 1 and 2 have a solver
+	pub implemented: [bool;2],
+	pub answer: Option<String>,
+	pub time: Option<Duration>,
+	pub tests: Option<Status>,
+}
+
+pub fn rows(ledger:&Ledger, timings:&Timings, tests:&HashMap<u8,Status>) -> Vec<Row> {
+	(1..=DAYS).map(|day| {
+		let latest = timings.latest(day);
+		Row {
+			day,
+			stars: ledger.stars(day),
+			implemented: [1,2].map(|part| days::solver(day, part).is_some()),
+			answer: latest.map(|t| t.answer.clone()),
+			time: latest.map(|t| t.duration),
+			tests: tests.get(&day).copied(),
+		}
+	})
+	.collect()
+}
+
+enum Message {
+	Ran(u8, Result<Vec<Run>,String>),
+	Tested(u8, Status),
+	Benched(u8, Status),
+}
+
+struct App {
+	session: String,
+	table: TableState,
+	tests: HashMap<u8,Status>,
+	status: String,
+	tx: mpsc::Sender<Message>,
+	rx: mpsc::Receiver<Message>,
+}
+
+pub fn show(session:String) -> io::Result<()> {
+
+	let hook = Arc::new(panic::take_hook());
+	let original = Arc::clone(&hook);
+	panic::set_hook(Box::new(move |info| {
+		if thread::current().name() != Some(WORKER) {
+			original(info)
+		}
+	}));
+
+	let terminal = ratatui::init();
+	let result = App::new(session).run(terminal);
+	ratatui::restore();
+
+	// dropping ours leaves the original hook with a single owner, to put back as it was
+	drop(panic::take_hook());
+	match Arc::try_unwrap(hook) {
+		Ok(hook) => panic::set_hook(hook),
+		Err(hook) => panic::set_hook(Box::new(move |info| hook(info))),
+	}
+	result
+}
+
+impl App {
+
+	fn new(session:String) -> Self {
+		let (tx,rx) = mpsc::channel();
+		App {
+			session,
+			table: TableState::default().with_selected(0),
+			tests: HashMap::new(),
+			status: "r: run  t: test  b: bench  q: quit".into(),
+			tx,
+			rx,
+		}
+	}
+
+	fn selected_day(&self) -> u8 {
+		u8::try_from(self.table.selected().unwrap_or(0)).unwrap() + 1
+	}
+
+	fn run(mut self, mut terminal:DefaultTerminal) -> io::Result<()> {
+		// the ledger and timings, loaded again only when a run may have changed them, or on a key press
+		let mut records = None;
+		loop {
+			if records.is_none() {
+				records = Some((Ledger::load(&Ledger::path())?, Timings::load(&Timings::path())?));
+			}
+			let (ledger, timings) = records.as_ref().unwrap();
+			let rows = rows(ledger, timings, &self.tests);
+			terminal.draw(|frame| self.draw(frame, &rows))?;
+
+			while let Ok(message) = self.rx.try_recv() {
+				if let Message::Ran(..) = message {
+					records = None;
+				}
+				self.receive(message);
+			}
+
+			if !event::poll(Duration::from_millis(250))? {
+				continue;
+			}
+			let Event::Key(key) = event::read()? else { continue };
+			if key.kind != KeyEventKind::Press {
+				continue;
+			}
+			records = None;
+			match key.code {
+				KeyCode::Char('q') | KeyCode::Esc => return Ok(()),
+				KeyCode::Down | KeyCode::Char('j') => self.table.select(Some((self.table.selected().unwrap_or(0) + 1).min(usize::from(DAYS) - 1))),
+				KeyCode::Up | KeyCode::Char('k') => self.table.select_previous(),
+				KeyCode::Char('r') => self.start_run(),
+				KeyCode::Char('t') => self.start_cargo("test", Message::Tested),
+				KeyCode::Char('b') => self.start_cargo("bench", Message::Benched),
+				_ => {}
+			}
+		}
+	}
+
+	fn receive(&mut self, message:Message) {
+		self.status = match message {
+			Message::Ran(day, Ok(runs)) => {
+				let answers:Vec<String> = runs.iter().map(|r| format!("part {}: {}", r.part, r.answer)).collect();
+				format!("Day {day} solved, {}", answers.join(", "))
+			}
+			Message::Ran(day, Err(e)) => format!("Day {day} failed: {e}"),
+			Message::Tested(day, status) => {
+				self.tests.insert(day, status);
+				format!("Day {day} tests {status:?}").to_lowercase()
+			}
+			Message::Benched(day, status) => format!("Day {day} benchmark {status:?}, see target/criterion").to_lowercase(),
+		}
+	}
+
+	fn start_run(&mut self) {
+		let day = self.selected_day();
+		let solvers:Vec<_> = SOLVERS.iter().filter(|s| s.day == day).collect();
+		if solvers.is_empty() {
+			self.status = format!("Day {day} has no solver yet");
+			return;
+		}
+		self.status = format!("Running day {day}...");
+		let session = self.session.clone();
+		let tx = self.tx.clone();
+		// there's no asking for confirmation in here, so only submit when that's always fine
+		let submit = config::get().submit == Submit::Always;
+		self.spawn(move || {
+			let runs = solvers
+				.into_iter()
+				.map(|solver| runner::run(&session, solver, submit))
+				.collect::<Result<Vec<Run>,_>>()
+				.map_err(|e| e.to_string());
+			let _ = tx.send(Message::Ran(day, runs));
+		});
+	}
+
+	// run `cargo test` or `cargo bench` for the selected day in the background
+	fn start_cargo(&mut self, command:&'static str, done:fn(u8,Status) -> Message) {
+		let day = self.selected_day();
+		if days::solver(day, 1).is_none() {
+			self.status = format!("Day {day} has no solver yet");
+			return;
+		}
+		if command == "test" {
+			self.tests.insert(day, Status::Running);
+		}
+		self.status = format!("Running cargo {command} for day {day}...");
+		let tx = self.tx.clone();
+		self.spawn(move || {
+			let filter = format!("day{day:02}");
+			let args:&[&str] = match command {
+				"test" => &["test", "--lib", "--quiet", &filter],
+				_ => &["bench", "--bench", "days", "--", &filter],
+			};
+			let ok = Command::new(env::var("CARGO").unwrap_or("cargo".into()))
+				.args(args)
+				.stdout(Stdio::null())
+				.stderr(Stdio::null())
+				.status()
+				.is_ok_and(|s| s.success());
+			let _ = tx.send(done(day, if ok { Status::Passed } else { Status::Failed }));
+		});
+	}
+
+	fn spawn(&self, f:impl FnOnce() + Send + 'static) {
+		thread::Builder::new().name(WORKER.into()).spawn(f).unwrap();
+	}
+
+	fn draw(&mut self, frame:&mut Frame, rows:&[Row]) {
+
+		let [table_area, status_area] = Layout::vertical([Constraint::Min(0), Constraint::Length(1)]).areas(frame.area());
+
+		let check = |b:bool| if b { "✓" } else { "·" };
+		let table_rows = rows.iter().map(|row| {
+			TableRow::new([
+				format!("{:2}", row.day),
+				"★".repeat(row.stars) + &"☆".repeat(2 - row.stars),
+				check(row.implemented[0]).into(),
+				check(row.implemented[1]).into(),
+				row.answer.clone().unwrap_or_default(),
+				row.time.map(|t| format!("{t:.1?}")).unwrap_or_default(),
+				row.tests.map(|s| format!("{s:?}").to_lowercase()).unwrap_or_default(),
+			])
+		});
+
+		let widths = [
+			Constraint::Length(3),
+			Constraint::Length(5),
+			Constraint::Length(6),
+			Constraint::Length(6),
+			Constraint::Min(16),
+			Constraint::Length(10),
+			Constraint::Length(8),
+		];
+
+		let table = Table::new(table_rows, widths)
+			.header(TableRow::new(["Day", "Stars", "Part 1", "Part 2", "Answer", "Time", "Tests"]).bold())
+			.row_highlight_style(Style::new().add_modifier(Modifier::REVERSED))
+			.block(Block::bordered().title(Line::from(format!(" Advent of Code {} ", days::YEAR)).bold()));
+
+		frame.render_stateful_widget(table, table_area, &mut self.table);
+		frame.render_widget(Line::from(self.status.as_str()), status_area);
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use pretty_assertions::assert_eq;
+	use crate::{ledger::{Submission, Verdict}, timings::Timing};
+	use super::*;
+
+	#[test]
+	fn test_rows() {
+		let mut ledger = Ledger::default();
+		ledger.record(Submission { day: 2, part: 1, answer: "8".into(), verdict: Verdict::Correct, at: 10 });
+		let mut timings = Timings::default();
+		timings.record(Timing { day: 2, part: 1, answer: "8".into(), duration: Duration::from_millis(3), at: 10 });
+		let tests = HashMap::from([(2, Status::Passed)]);
+
+		let rows = rows(&ledger, &timings, &tests);
+		assert_eq!(rows.len(), 25);
+		assert_eq!(rows[1], Row {
+			day: 2,
+			stars: 1,
+			implemented: [true,false],
+			answer: Some("8".into()),
+			time: Some(Duration::from_millis(3)),
+			tests: Some(Status::Passed),
+		});
+		assert_eq!(rows[24].implemented, [false,false]);
+	}
+}
@@ -6,3 +6,67 @@ pub mod day05;
 pub mod day06;
 pub mod day07;
 pub mod day08;
+
+pub const YEAR:u16 = 2023;
+
+/// A solver for one part of a day's puzzle
+pub struct Solver {
+	pub day: u8,
+	pub part: u8,
+	pub solve: fn(&str) -> String,
+}
+
+/// Every implemented solver, in calendar order
+pub static SOLVERS: &[Solver] = &[
+	Solver { day: 1, part: 1, solve: day01::solve },
+	Solver { day: 2, part: 1, solve: day02::solve },
+	Solver { day: 3, part: 1, solve: day03::solve },
+	Solver { day: 4, part: 1, solve: day04::solve },
+	Solver { day: 5, part: 1, solve: day05::solve },
+	Solver { day: 6, part: 1, solve: day06::solve },
+	Solver { day: 7, part: 1, solve: day07::solve },
+	Solver { day: 8, part: 1, solve: day08::solve },
+];
+
+/// Puzzle titles, by day
+pub static TITLES: &[&str] = &[
+	"Trebuchet?!",
+	"Cube Conundrum",
+	"Gear Ratios",
+	"Scratchcards",
+	"If You Give A Seed A Fertilizer",
+	"Wait For It",
+	"Camel Cards",
+	"Haunted Wasteland",
+];
+
+pub fn title(day:u8) -> Option<&'static str> {
+	TITLES.get(usize::from(day).checked_sub(1)?).copied()
+}
+
+pub fn solver(day:u8, part:u8) -> Option<&'static Solver> {
+	SOLVERS.iter().find(|s| s.day == day && s.part == part)
+}
+
+#[cfg(test)]
+mod fixtures {
+	use std::{fs, path::Path};
+	use pretty_assertions::assert_str_eq;
+
+	/// Solve an example from examples/2023, and compare with the answer next to it,
+	/// also with CRLF line endings, as a checkout with git's autocrlf has them
+	fn check(day:u8, part:u8, name:&str) {
+		let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("examples").join(super::YEAR.to_string());
+		let read = |ext:&str| {
+			let path = dir.join(format!("{name}.{ext}"));
+			fs::read_to_string(&path).unwrap_or_else(|e| panic!("can't read {}: {e}", path.display()))
+		};
+		let solver = super::solver(day, part).unwrap_or_else(|| panic!("there's no solver for day {day} part {part}"));
+		let (example, answer) = (read("txt"), read("answer"));
+		assert_str_eq!((solver.solve)(&example), answer.trim_end());
+		assert_str_eq!((solver.solve)(&example.replace('\n', "\r\n")), answer.trim_end());
+	}
+
+	// a test for every example, generated by build.rs
+	include!(concat!(env!("OUT_DIR"), "/fixtures.rs"));
+}
@@ -1,16 +1,27 @@
 // https://adventofcode.com/2023/day/1
 
 use std::str;
+use tracing::{debug, instrument};
 
-pub fn solve(input: &str) -> String {
-	input
+#[instrument(name = "parse", skip_all)]
+pub fn parse(input: &str) -> Vec<Vec<u32>> {
+	let lines:Vec<Vec<u32>> = input
 		.lines()
-		.map(|l| {
-			// collect digits
-			let digits:Vec<&str> = l.matches(char::is_numeric).collect();
+		// collect digits
+		.map(|l| l.chars().filter_map(|c| c.to_digit(10)).collect())
+		.collect();
+	debug!(lines = lines.len(), "parsed");
+	lines
+}
+
+#[instrument(name = "solve", skip_all, fields(part = 1))]
+pub fn part1(lines: &[Vec<u32>]) -> String {
+	lines
+		.iter()
+		.map(|digits| {
 			// combine first+last digits into a u32
 			if let (Some(f),Some(l)) = (digits.first(),digits.last()) {
-				format!("{f}{l}").parse::<u32>().unwrap()
+				f*10 + l
 			} else {
 				panic!("Expected at least one digit in the line")
 			}
@@ -20,6 +31,10 @@ pub fn solve(input: &str) -> String {
 		.to_string()
 }
 
+pub fn solve(input: &str) -> String {
+	part1(&parse(input))
+}
+
 #[cfg(test)]
 mod tests {
 	use super::solve;
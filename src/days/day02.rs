@@ -1,6 +1,10 @@
 // https://adventofcode.com/2023/day/2
 
-mod hand {
+pub mod hand {
+
+	use std::ops::Add;
+
+	use crate::utils::parse::{self, Cursor};
 
 	#[derive(Default,PartialEq,Debug)]
 	pub struct Hand {
@@ -11,30 +15,31 @@ mod hand {
 
 	impl Hand {
 		pub fn new(input:&str) -> Self {
-			// parse "<part>, <part>, (...)"
-			input
-			.split(", ")
-			.map(|c| {
-				// parse "<n> <color>"
-				let mut parts = c.split_whitespace();
-				let amount = parts.next();
-				let color  = parts.next();
-				if let (Some(left),Some(right)) = (amount,color) {
-					let n = left.parse::<u32>().unwrap();
-					match right {
-						"red"   => Hand { red:   n, ..Default::default() },
-						"green" => Hand { green: n, ..Default::default() },
-						"blue"  => Hand { blue:  n, ..Default::default() },
-						_ => panic!("Invalid color")
-					}
-				} else {
-					panic!("Invalid \"<n> <color>\" pair")
-				}
-			})
-			// combine the partial hands, by adding them
-			.fold(Hand::default(),Hand::add)
+			parse::all(input, hand).unwrap_or_else(|e| panic!("{e}"))
 		}
-		pub fn add(self,other:Hand) -> Hand {
+	}
+
+	/// "<n> <color>, <n> <color>, (...)"
+	pub fn hand(c:&mut Cursor) -> parse::Result<Hand> {
+		// a hand of <n> cubes, by color
+		type Cubes = fn(u32) -> Hand;
+		let colors:[(&str,Cubes);3] = [
+			("red",   |n| Hand { red:   n, ..Default::default() }),
+			("green", |n| Hand { green: n, ..Default::default() }),
+			("blue",  |n| Hand { blue:  n, ..Default::default() }),
+		];
+		let cubes = c.list(", ", |c| {
+			let n = c.number()?;
+			c.tag(" ")?;
+			Ok(c.one_of(&colors)?(n))
+		})?;
+		// combine the partial hands, by adding them
+		Ok(cubes.into_iter().fold(Hand::default(),Hand::add))
+	}
+
+	impl Add for Hand {
+		type Output = Hand;
+		fn add(self,other:Hand) -> Hand {
 			Hand {
 				red: self.red + other.red,
 				green: self.green + other.green,
@@ -58,10 +63,10 @@ mod hand {
 	}
 }
 
-mod game {
+pub mod game {
 
 	use super::hand::*;
-	use std::result::Result;
+	use crate::utils::parse::{self, Cursor};
 
 	#[derive(PartialEq,Debug)]
 	pub struct Game {
@@ -71,23 +76,17 @@ mod game {
 
 	impl Game {
 		pub fn new(input:&str) -> Game {
-
-			let mut parts = input.split(": ");
-			let header = parts.next().unwrap();
-			let body  = parts.next().unwrap();
-
-			// parse "Game <n>"
-			let id:u32 = header
-				.split_whitespace().last()
-				.map(str::parse::<u32>)
-				.and_then(Result::ok).unwrap();
-
-			// parse list of hands
-			let hands:Vec<Hand> = body.split("; ").map(Hand::new).collect();
-			Game { id, hands }
+			parse::all(input, game).unwrap_or_else(|e| panic!("{e}"))
 		}
 	}
 
+	/// "Game <n>: <hand>; <hand>; (...)"
+	pub fn game(c:&mut Cursor) -> parse::Result<Game> {
+		let id = c.header("Game")?;
+		let hands = c.list("; ", hand)?;
+		Ok(Game { id, hands })
+	}
+
 	#[cfg(test)]
 	mod tests {
 		use pretty_assertions::assert_eq;
@@ -111,17 +110,31 @@ mod game {
 }
 
 use game::Game;
+use tracing::{debug, instrument};
 
-pub fn solve(input: &str) -> String {
+use crate::utils::parse;
+
+pub fn try_parse(input: &str) -> parse::Result<Vec<Game>> {
+	parse::all(input, |c| c.lines(game::game))
+}
+
+#[instrument(name = "parse", skip_all)]
+pub fn parse(input: &str) -> Vec<Game> {
+	let games = try_parse(input).unwrap_or_else(|e| panic!("{e}"));
+	debug!(games = games.len(), "parsed");
+	games
+}
+
+#[instrument(name = "solve", skip_all, fields(part = 1))]
+pub fn part1(games: &[Game]) -> String {
 
 	// no hand should have more than this amount of any colored cubes
 	const MAX_RED:u32 = 12;
 	const MAX_GREEN:u32 = 13;
 	const MAX_BLUE:u32 = 14;
 
-	input
-	.lines()
-	.map(Game::new)
+	games
+	.iter()
 	.filter(|game| {
 		// all hands should have no more colored cubes than the max asserted
 		game.hands
@@ -132,11 +145,16 @@ pub fn solve(input: &str) -> String {
 			hand.blue  <= MAX_BLUE
 		)
 	})
+	.inspect(|game| debug!(id = game.id, "possible"))
 	.map(|game| game.id)
 	.sum::<u32>()
 	.to_string()
 }
 
+pub fn solve(input: &str) -> String {
+	part1(&parse(input))
+}
+
 #[cfg(test)]
 mod tests {
 	use pretty_assertions::assert_str_eq;
@@ -1,135 +1,85 @@
 // https://adventofcode.com/2023/day/3
 
-#[derive(Debug,Clone)]
-struct Vec2 {
-	x:u32,
-	y:u32,
-}
-
-impl Vec2 {
-	pub fn distance_to(&self,v:&Vec2) -> Distance {
-		Distance
-			{ x: self.x.abs_diff(v.x)
-			, y: self.y.abs_diff(v.y)
-			}
-	}
-}
+use tracing::{debug, instrument};
 
-type Position = Vec2;
-type Distance = Vec2;
+use crate::utils::{bits::BitGrid, grid::{Grid, Pos}, union_find::KeyedUnionFind};
 
-#[derive(Debug,Clone)]
-enum Value {
+#[derive(Debug,Clone,Copy,PartialEq,Eq)]
+pub enum Value {
 	Digit(u32),
 	Symbol,
 	Void,
 }
 
-#[derive(Debug,Clone)]
-struct Cell {
-	pos: Position,
-	val: Value
-}
-impl Cell {
-	pub fn new(pos:Position,chr:char) -> Self {
-		Cell {
-			pos,
-			val: match chr {
-				'.'  => Value::Void,
-				chr  if chr.is_ascii_digit() => Value::Digit(chr.to_digit(10).unwrap()),
-				_chr => Value::Symbol
-			}
+impl From<char> for Value {
+	fn from(chr:char) -> Self {
+		match chr {
+			'.'  => Value::Void,
+			chr  if chr.is_ascii_digit() => Value::Digit(chr.to_digit(10).unwrap()),
+			_chr => Value::Symbol
 		}
 	}
-	pub fn is_adjacent_to(&self, c:&Cell) -> bool {
-		let d = self.pos.distance_to(&c.pos);
-		std::cmp::max(d.x,d.y) <= 1
-	}
 }
 
-#[derive(Debug,Clone)]
-struct PartNo {
-	val: u32,
-	cells: Vec<Cell>,
-}
-impl PartNo {
-	pub fn new(cell:Cell) -> Self {
-		let Value::Digit(val) = cell.val else { panic!() };
-		PartNo { val, cells: vec![cell] }
-	}
-	pub fn grow(&self, cell:Cell) -> Self {
-		let Value::Digit(val) = cell.val else { panic!() };
-		PartNo {
-			val : self.val*10 + val,
-			cells: [self.cells.clone(),vec![cell]].concat()
-		}
-	}
+/// A number in the schematic, and where its digits are
+#[derive(Debug,Clone,PartialEq,Eq)]
+pub struct PartNo {
+	pub val: u32,
+	pub cells: Vec<Pos>,
 }
 
-fn parse(input: &str) -> (Vec<PartNo>,Vec<Cell>) {
-
-	let mut partnos:Vec<PartNo> = vec![];
-	let mut symbols:Vec<Cell>   = vec![];
-	let eol = || std::iter::once('.');
-
-	input
-		.lines()
-		.enumerate()
-		.for_each(|(i,row)| {
-
-			// Build a part number as we go
-
-			let mut partno_partial:Option<PartNo> = None;
+/// The numbers in the schematic, row by row: each is a set of digits joined to the ones on their left
+pub fn partnos(schematic:&Grid<Value>) -> Vec<PartNo> {
 
-			row
-			.chars()
-			.chain(eol()) // add terminator value, to make trailing 'partnos' enclosed
-			.enumerate()
-			.for_each(|(j,c)| {
-				let y = u32::try_from(i).unwrap();
-				let x = u32::try_from(j).unwrap();
-				let cell = Cell::new(Vec2 { x, y }, c);
+	let digit = |pos:Pos| match schematic[pos] { Value::Digit(d) => Some(d), _ => None };
 
-				if matches!(cell.val,Value::Digit(_)){
-
-					if let Some(partno) = &partno_partial {
-						partno_partial = Some(partno.grow(cell));
-					} else {
-						partno_partial = Some(PartNo::new(cell));
-					}
-
-				} else {
-
-					if matches!(cell.val,Value::Symbol) {
-						symbols.push(cell.clone());
-					}
+	// digits only, added row by row, so that sets list them in reading order
+	let mut numbers = KeyedUnionFind::new();
+	for ((x,y),_) in schematic.iter().filter(|&(pos,_)| digit(pos).is_some()) {
+		numbers.insert((x,y));
+		if x > 0 && digit((x-1,y)).is_some() {
+			numbers.union((x-1,y), (x,y));
+		}
+	}
 
-					if let Some(partno) = &partno_partial {
-						partnos.push(partno.clone());
-						partno_partial = None;
-					}
-				}
-			});
-		});
+	numbers.components()
+		.into_iter()
+		.map(|cells| PartNo {
+			val: cells.iter().fold(0, |val, &pos| val*10 + digit(pos).unwrap()),
+			cells,
+		})
+		.collect()
+}
 
-	(partnos,symbols)
+#[instrument(name = "parse", skip_all)]
+pub fn parse(input: &str) -> Grid<Value> {
+	let schematic = Grid::parse(input, Value::from);
+	debug!(width = schematic.width(), height = schematic.height(), "parsed");
+	schematic
 }
 
-pub fn solve(input:&str) -> String {
+#[instrument(name = "solve", skip_all, fields(part = 1))]
+pub fn part1(schematic: &Grid<Value>) -> String {
+
+	let partnos = partnos(schematic);
+	debug!(partnos = partnos.len(), "found");
 
-	let (partnos,symbols) = parse(input);
+	// every cell next to a symbol, all at once
+	let symbols = BitGrid::from_fn(schematic.width(), schematic.height(), |p| schematic[p] == Value::Symbol);
+	let near = symbols.neighbours8();
 
 	partnos
 		.iter()
-		.filter(|pn| {
-			pn.cells.iter()
-			.any(|p| symbols.iter().any(|s| s.is_adjacent_to(p)))
-		})
+		.filter(|pn| pn.cells.iter().any(|&p| near.get(p)))
 		.map(|pn| pn.val )
 		.sum::<u32>()
 		.to_string()
 }
 
+pub fn solve(input:&str) -> String {
+	part1(&parse(input))
+}
+
 #[cfg(test)]
 mod tests {
 	use pretty_assertions::assert_str_eq;
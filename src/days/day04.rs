@@ -2,45 +2,32 @@
 
 use std::str;
 
-mod card {
+pub mod card {
+
+	use crate::utils::{bits::BitSet, parse::{self, Cursor}};
+
+	// numbers below this are also kept as bit sets, to count the matches with a popcount
+	const SMALL:u32 = 1024;
 
 	pub struct Card {
 		winn : Vec<u32>,
-		hand : Vec<u32>
+		hand : Vec<u32>,
+		sets : Option<(BitSet,BitSet)>
 	}
 
 	impl Card {
 
 		pub fn new(input:&str) -> Self {
-
-			// parse "<winn1> <winn2> (...) | <draw1> <draw2> (...)"
-
-			let mut parts = input
-			.split('|')
-			.map(|p| {
-				p.split_whitespace()
-				.filter_map(|n| n.parse::<u32>().ok())
-				.collect::<Vec<u32>>()
-			});
-
-			let winners = parts.next().expect("There should be a winners list");
-			let draw    = parts.next().expect("There should be a draw list");
-
-			Card {
-				winn: winners.clone(),
-				hand: draw.clone()
-			}
+			parse::all(input, numbers).unwrap_or_else(|e| panic!("{e}"))
 		}
 
 		pub fn score(&self) -> u32 {
-			let (win,_) : (Vec<u32>,_) =
-				self.hand
-				.iter()
-				.partition(|&d|
-					self.winn.iter().any(|w| w==d)
-				);
-
-			match win.len() {
+			let matches = match &self.sets {
+				Some((winn,hand)) => winn.intersection_count(hand),
+				None => self.hand.iter().filter(|&d| self.winn.contains(d)).count(),
+			};
+
+			match matches {
 				c if c >= 1 => {
 					let exp:u32 = c.try_into().unwrap();
 					2_u32.pow(exp-1)
@@ -50,6 +37,31 @@ mod card {
 		}
 	}
 
+	impl From<(Vec<u32>,Vec<u32>)> for Card {
+		fn from((winn, hand):(Vec<u32>,Vec<u32>)) -> Self {
+			let set = |ns:&[u32]| ns.iter().map(|&n| n as usize).collect::<BitSet>();
+			let small = winn.iter().chain(&hand).all(|&n| n < SMALL);
+			// a number drawn twice would only count once in a set
+			let sets = small.then(|| (set(&winn), set(&hand))).filter(|(_,h)| h.len() == hand.len());
+			Card { winn, hand, sets }
+		}
+	}
+
+	/// "<winn1> <winn2> (...) | <draw1> <draw2> (...)"
+	pub fn numbers(c:&mut Cursor) -> parse::Result<Card> {
+		let winn = c.numbers()?;
+		c.spaces();
+		c.tag("|")?;
+		let hand = c.numbers()?;
+		Ok(Card::from((winn, hand)))
+	}
+
+	/// "Card <n>: <numbers>"
+	pub fn card(c:&mut Cursor) -> parse::Result<Card> {
+		c.header::<u32>("Card")?;
+		numbers(c)
+	}
+
 	#[cfg(test)]
 	mod tests {
 		use pretty_assertions::assert_eq;
@@ -60,44 +72,63 @@ mod card {
 			let expected = 4;
 			let actual = Card::new("10 27 43 57 | 4 10 14 27 43 55").score();
 			assert_eq!(actual, expected);
+
+			// without the sets, for large or repeated numbers
+			assert_eq!(Card::new("10 27 4300 57 | 4 10 14 27 4300 55").score(), expected);
+			assert_eq!(Card::new("10 27 43 57 | 4 10 10 27 43 55").score(), 8);
 		}
 	}
 }
 
-mod game {
+pub mod game {
 
-	use super::card::Card;
+	use super::card::{self, Card};
+	use crate::utils::parse::{self, Cursor};
 
 	pub struct Game { pub cards:Vec<Card> }
 	impl Game {
 		pub fn new(input:&str) -> Self {
-			let cards = input
-				.lines()
-				.map(|l| {
-					l.split(':')
-					.last()
-					.map(Card::new)
-					.unwrap()
-				})
-				.collect();
-
-			Game { cards }
+			parse::all(input, game).unwrap_or_else(|e| panic!("{e}"))
 		}
 	}
+
+	/// A card per line
+	pub fn game(c:&mut Cursor) -> parse::Result<Game> {
+		Ok(Game { cards: c.lines(card::card)? })
+	}
 }
 
 use card::Card;
 use game::Game;
+use tracing::{debug, instrument};
 
-pub fn solve(input: &str) -> String {
+use crate::utils::parse;
+
+pub fn try_parse(input: &str) -> parse::Result<Game> {
+	parse::all(input, game::game)
+}
 
-	Game::new(input).cards
+#[instrument(name = "parse", skip_all)]
+pub fn parse(input: &str) -> Game {
+	let game = try_parse(input).unwrap_or_else(|e| panic!("{e}"));
+	debug!(cards = game.cards.len(), "parsed");
+	game
+}
+
+#[instrument(name = "solve", skip_all, fields(part = 1))]
+pub fn part1(game: &Game) -> String {
+
+	game.cards
 	.iter()
 	.map(Card::score)
 	.sum::<u32>()
 	.to_string()
 }
 
+pub fn solve(input: &str) -> String {
+	part1(&parse(input))
+}
+
 #[cfg(test)]
 mod tests {
 	use super::solve;
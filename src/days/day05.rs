@@ -1,11 +1,13 @@
 // https://adventofcode.com/2023/day/5
 
-mod map {
+pub mod map {
 
-	use std::{ops::Range, str::Lines, iter};
+	use std::{ops::Range, iter};
 
-	#[derive(PartialEq,Debug)]
-	enum Step {
+	use crate::utils::{parse::{self, Cursor}, ranges::{self, RangeSet}};
+
+	#[derive(PartialEq,Debug,Clone,Copy)]
+	pub enum Step {
 		Seed,
 		Soil,
 		Fertilizer,
@@ -16,24 +18,16 @@ mod map {
 		Location
 	}
 
-	impl TryFrom<&str> for Step {
-
-		type Error = &'static str;
-
-		fn try_from(s:&str) -> Result<Self,Self::Error> {
-			match s {
-				"seed"        => Ok(Step::Seed),
-				"soil"        => Ok(Step::Soil),
-				"fertilizer"  => Ok(Step::Fertilizer),
-				"water"       => Ok(Step::Water),
-				"light"       => Ok(Step::Light),
-				"temperature" => Ok(Step::Temperature),
-				"humidity"    => Ok(Step::Humidity),
-				"location"    => Ok(Step::Location),
-				_             => Err("")
-			}
-		}
-	}
+	const STEPS:[(&str,Step);8] = [
+		("seed",        Step::Seed),
+		("soil",        Step::Soil),
+		("fertilizer",  Step::Fertilizer),
+		("water",       Step::Water),
+		("light",       Step::Light),
+		("temperature", Step::Temperature),
+		("humidity",    Step::Humidity),
+		("location",    Step::Location),
+	];
 
 	#[derive(Debug)]
 	struct Mapper {
@@ -47,22 +41,22 @@ mod map {
 		}
 	}
 
-	impl From<&str> for Mapper {
-		fn from(line:&str) -> Self {
-			let mut parts = line.split_whitespace().flat_map(str::parse::<u64>);
-			let dst:u64 = parts.next().unwrap();
-			let src:u64 = parts.next().unwrap();
-			let len:u64 = parts.next().unwrap();
-
-			let start = src;
-			let end:u64 = start+len;
-			let range = start..end;
-			let idst:i64 = dst.try_into().unwrap();
-			let isrc:i64 = src.try_into().unwrap();
-			let offset:i64 = idst-isrc;
-
-			Mapper { range, offset }
-		}
+	/// "<dst> <src> <len>"
+	fn mapper(c:&mut Cursor) -> parse::Result<Mapper> {
+		let dst:u64 = c.number()?;
+		c.tag(" ")?;
+		let src:u64 = c.number()?;
+		c.tag(" ")?;
+		let len:u64 = c.number()?;
+
+		let start = src;
+		let end:u64 = start+len;
+		let range = start..end;
+		let idst:i64 = dst.try_into().unwrap();
+		let isrc:i64 = src.try_into().unwrap();
+		let offset:i64 = idst-isrc;
+
+		Ok(Mapper { range, offset })
 	}
 
 	impl Mapper {
@@ -76,45 +70,43 @@ mod map {
 				None
 			}
 		}
+
+		pub fn map_range(&self,range:Range<u64>) -> Range<u64> {
+			let shift = |n:u64| n.checked_add_signed(self.offset).unwrap();
+			shift(range.start)..shift(range.end)
+		}
 	}
 
 	#[derive(Debug)]
 	pub struct Map {
-		src: Step,
-		dst: Step,
+		pub src: Step,
+		pub dst: Step,
 		mps: Vec<Mapper>
 	}
 
-	impl<'a> TryFrom<&mut Lines<'a>> for Map {
-
-		type Error = ();
-
-		fn try_from(lines:&mut Lines) -> Result<Self,Self::Error> {
+	/// "<src>-to-<dst> map:", then a mapper per line
+	pub fn map(c:&mut Cursor) -> parse::Result<Map> {
 
-			match lines.next() {
-				None => Err(()),
-				Some(header) => {
+		let src = c.one_of(&STEPS)?;
+		c.tag("-to-")?;
+		let dst = c.one_of(&STEPS)?;
+		c.tag(" map:")?;
+		c.eol()?;
 
-					// decode "<src>-to-<dst> map:"
+		let mps = c.lines(mapper)?
+			.into_iter()
+			// add default fallback mapper
+			.chain(iter::once_with(Default::default))
+			.collect::<Vec<Mapper>>();
+		tracing::trace!(?src, ?dst, ranges = mps.len() - 1, "map");
 
-					let (src,dst)= header.split_whitespace().take(1).next()
-					.map(|mapping| {
-						// taking 3, filter map will ignore the <to>
-						let mut parts = mapping.split('-').take(3).filter_map(|p| Step::try_from(p).ok());
-						(parts.next().expect(""), parts.next().unwrap())
-					}).unwrap();
-
-					// decode mappings
-
-					let mps = lines.take_while(|l| !l.is_empty())
-					.map(Mapper::from)
-					// add default fallback mapper
-					.chain(iter::once_with(Default::default))
-					.collect();
+		Ok(Map { src, dst, mps })
+	}
 
-					Ok(Map { src, dst, mps })
-				}
-			}
+	impl TryFrom<&str> for Map {
+		type Error = parse::Error;
+		fn try_from(block:&str) -> parse::Result<Self> {
+			parse::all(block, map)
 		}
 	}
 
@@ -122,14 +114,24 @@ mod map {
 		pub fn map(&self,v:u64) -> u64 {
 			self.mps.iter().find_map(|m| m.map(v)).unwrap()
 		}
+
+		/// Map whole ranges at once, cutting them where the mappers' ranges start and end
+		pub fn map_ranges(&self,set:&RangeSet<u64>) -> RangeSet<u64> {
+			let cuts:Vec<Range<u64>> = self.mps.iter().map(|m| m.range.clone()).collect();
+			set.iter()
+			.flat_map(|range| ranges::split(range, &cuts))
+			.map(|(piece,mapper)| match mapper {
+				Some(i) => self.mps[i].map_range(piece),
+				None    => piece,
+			})
+			.collect()
+		}
 	}
 
 	#[cfg(test)]
 	mod tests {
 
-		use std::borrow::BorrowMut;
-
-use pretty_assertions::assert_eq;
+		use pretty_assertions::assert_eq;
 		use super::*;
 
 		#[test]
@@ -153,44 +155,64 @@ r###"
 98    50
 99    51
 "###.trim_start();
-			let map = Map::try_from(input.lines().borrow_mut()).unwrap();
+			let map = Map::try_from(input).unwrap();
 			expected.lines().map(str::split_whitespace).for_each(|v| {
 				let mut vs = v.flat_map(str::parse::<u64>);
 				let src:u64 = vs.next().unwrap();
 				let dst:u64 = vs.next().unwrap();
 				assert_eq!(map.map(src),dst);
 			});
+
+			// ranges are mapped like every value in them
+			let seeds:RangeSet<u64> = [0..10, 45..60, 95..105].into_iter().collect();
+			let mapped:RangeSet<u64> = seeds.iter().flatten().map(|v| map.map(v)).map(|v| v..v+1).collect();
+			assert_eq!(map.map_ranges(&seeds), mapped);
 		}
 	}
 }
 
-use std::borrow::BorrowMut;
 use crate::days::day05::map::Map;
+use crate::utils::{parse, ranges::RangeSet};
+use tracing::{debug, instrument};
+
+/// "seeds: <v1> <v2> <v3>", then blocks of maps
+pub fn try_parse(input: &str) -> parse::Result<(Vec<u64>,Vec<Map>)> {
+	parse::all(input, |c| {
+		let seeds = c.field("seeds", |c| c.numbers())?;
+		c.eol()?;
+		c.eol()?;
+		let maps = c.blocks(map::map)?;
+		Ok((seeds,maps))
+	})
+}
 
-pub fn solve(input: &str) -> String {
+#[instrument(name = "parse", skip_all)]
+pub fn parse(input: &str) -> (Vec<u64>,Vec<Map>) {
 
-	let mut lines = input.lines();
+	let (seeds,maps) = try_parse(input).unwrap_or_else(|e| panic!("{e}"));
 
-	// parse "seeds: <v1> <v2> <v3>"
-	let seeds:Vec<u64> = lines.next().unwrap().split(':')
-	.nth(1).unwrap().split_whitespace()
-	.filter_map(|p| p.parse().ok()).collect();
+	debug!(seeds = seeds.len(), maps = maps.len(), "parsed");
+	(seeds,maps)
+}
 
-	// skip empty line
-	lines.next();
+#[instrument(name = "solve", skip_all, fields(part = 1))]
+pub fn part1(almanac: &(Vec<u64>,Vec<Map>)) -> String {
 
-	let mut maps:Vec<Map> = vec![];
+	let (seeds,maps) = almanac;
 
-	// parse blocks into maps
-	while let Ok(map) = Map::try_from(lines.borrow_mut()) {
-		maps.push(map)
-	}
+	// every seed as a range of one, mapped through all the maps at once
+	let seeds:RangeSet<u64> = seeds.iter().map(|&s| s..s+1).collect();
+	let locations = maps.iter().fold(seeds,|prev,m| {
+		let next = m.map_ranges(&prev);
+		debug!(dst = ?m.dst, ranges = next.ranges().len(), "mapped");
+		next
+	});
 
-	seeds.iter().map(|s|
-		maps.iter().fold(*s,|prev,m| m.map(prev))
-	)
-	.min().unwrap()
-	.to_string()
+	locations.min().unwrap().to_string()
+}
+
+pub fn solve(input: &str) -> String {
+	part1(&parse(input))
 }
 
 #[cfg(test)]
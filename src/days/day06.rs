@@ -1,7 +1,11 @@
 // https://adventofcode.com/2023/day/6
 
+use tracing::{debug, instrument};
+
+use crate::utils::parse;
+
 #[derive(PartialEq,Debug)]
-struct BestRace {
+pub struct BestRace {
 	time: u32,
 	dist: u32,
 }
@@ -12,30 +16,30 @@ impl From<(u32,u32)> for BestRace {
 	}
 }
 
-pub fn solve(input: &str) -> String {
-
-	let mut row_values = input.lines().map(|line| {
-		let raw_values = line.split(':').last().unwrap();
-		raw_values
-			.split_whitespace()
-			.map(str::parse::<u32>)
-			.filter_map(Result::ok)
-			.collect::<Vec<u32>>()
-	});
+/// "Time: <t1> <t2> (...)", then "Distance: <d1> <d2> (...)"
+pub fn try_parse(input: &str) -> parse::Result<Vec<BestRace>> {
+	parse::all(input, |c| {
+		let times:Vec<u32> = c.field("Time", |c| c.numbers())?;
+		c.eol()?;
+		let distances:Vec<u32> = c.field("Distance", |c| c.numbers())?;
+		c.eol()?;
+		Ok(times.into_iter().zip(distances).map(BestRace::from).collect())
+	})
+}
 
-	let times = row_values.next().unwrap();
-	let distances = row_values.next().unwrap();
+#[instrument(name = "parse", skip_all)]
+pub fn parse(input: &str) -> Vec<BestRace> {
+	let races = try_parse(input).unwrap_or_else(|e| panic!("{e}"));
+	debug!(races = races.len(), "parsed");
+	races
+}
 
-	let best_races =
-		times
-		.into_iter()
-		.zip(distances)
-		.map(BestRace::from)
-		.collect::<Vec<BestRace>>();
+#[instrument(name = "solve", skip_all, fields(part = 1))]
+pub fn part1(best_races: &[BestRace]) -> String {
 
 	best_races
-		.into_iter()
-		.map(| BestRace { time: time_limit, dist: distance_record } |
+		.iter()
+		.map(|&BestRace { time: time_limit, dist: distance_record }|
 
 			(0..=time_limit.to_owned())
 			.map(|time_pressed| {
@@ -48,11 +52,16 @@ pub fn solve(input: &str) -> String {
 			.filter(|(_,d)| d > &distance_record)
 			.count()
 
-		).
+		)
+		.inspect(|ways| debug!(ways, "ways to win")).
 		product::<usize>()
 		.to_string()
 }
 
+pub fn solve(input: &str) -> String {
+	part1(&parse(input))
+}
+
 #[cfg(test)]
 mod tests {
 	use pretty_assertions::assert_str_eq;
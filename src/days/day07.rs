@@ -2,7 +2,7 @@
 
 use std::str;
 
-mod card {
+pub mod card {
 
 	static GRADE:[char;13] = ['A','K','Q','J','T','9','8','7','6','5','4','3','2'];
 
@@ -48,7 +48,7 @@ mod card {
 	}
 }
 
-mod hand {
+pub mod hand {
 
 	use super::card::Card;
 
@@ -66,8 +66,8 @@ mod hand {
 	#[derive(Debug,PartialEq,Eq)]
 	pub struct Hand {
 		pub bid: u32,
-		cards: [Card;5],
-		category: Category,
+		pub cards: [Card;5],
+		pub category: Category,
 	}
 
 	impl Ord for Hand {
@@ -142,10 +142,19 @@ mod hand {
 }
 
 use hand::Hand;
+use tracing::{debug, instrument};
 
-pub fn solve(input: &str) -> String {
+#[instrument(name = "parse", skip_all)]
+pub fn parse(input: &str) -> Vec<Hand> {
+	let hands:Vec<Hand> = input.lines().map(Hand::new).collect();
+	debug!(hands = hands.len(), "parsed");
+	hands
+}
+
+#[instrument(name = "solve", skip_all, fields(part = 1))]
+pub fn part1(hands: &[Hand]) -> String {
 
-	let mut hands:Vec<Hand> = input.lines().map(Hand::new).collect();
+	let mut hands:Vec<&Hand> = hands.iter().collect();
 	hands.sort();
 	hands.reverse();
 
@@ -158,6 +167,10 @@ pub fn solve(input: &str) -> String {
 	total.to_string()
 }
 
+pub fn solve(input: &str) -> String {
+	part1(&parse(input))
+}
+
 #[cfg(test)]
 mod tests {
 	use super::solve;
@@ -1,66 +1,121 @@
 // https://adventofcode.com/2023/day/8
 
-use std::{str, collections::HashMap};
+use std::{str, fmt};
+use tracing::{debug, instrument};
 
-type Id = [char;3];
+use crate::utils::{graph::Graph, parse::{self, Cursor, Expected}};
+
+pub type Id = [char;3];
 #[derive(PartialEq, Eq, Hash, Debug, Clone, Copy)]
-struct Node(Id);
+pub struct Node(Id);
 
-impl From<&str> for Node {
-   fn from(value: &str) -> Self {
-		let chars: Vec<char> = value.chars().take(3).collect();
-		let id: Id = chars.try_into().unwrap();
-		Node(id)
+impl fmt::Display for Node {
+	fn fmt(&self, f:&mut fmt::Formatter) -> fmt::Result {
+		write!(f, "{}", self.0.iter().collect::<String>())
 	}
 }
 
-pub fn solve(input: &str) -> String {
-	let mut lines = input.lines();
+/// The directions to take, and the nodes, each with an edge to its left then its right node
+pub type Network = (Vec<char>,Graph<Node>);
+
+/// "<name>", three letters or digits
+fn node(c:&mut Cursor) -> parse::Result<Node> {
+	let start = c.clone();
+	let name = c.identifier()?;
+	let id:Id = name.chars().collect::<Vec<char>>().try_into().map_err(|_| start.error(Expected::Identifier))?;
+	Ok(Node(id))
+}
 
-	let directions = lines.next().unwrap().chars().cycle();
-	let _separator = lines.next();
+/// The directions, a blank line, then "<node> = (<left>, <right>)" lines
+pub fn try_parse(input: &str) -> parse::Result<Network> {
+	parse::all(input, |c| {
+		let directions = c.take_while(|d| d == 'L' || d == 'R').chars().collect();
+		c.eol()?;
+		c.eol()?;
+
+		// where each node is, to point at the one that's wrong
+		let paths = c.lines(|c| {
+			let at = c.clone();
+			let from = node(c)?;
+			c.tag(" = (")?;
+			let left = (c.clone(), node(c)?);
+			c.tag(", ")?;
+			let right = (c.clone(), node(c)?);
+			c.tag(")")?;
+			Ok((at,from,[left,right]))
+		})?;
+
+		// every node is defined once, so that it has a left and a right node to go to
+		let mut graph = Graph::new();
+		for (at,from,_) in &paths {
+			if graph.node(from).is_some() {
+				return Err(at.error(Expected::Other("a node that isn't defined yet")));
+			}
+			graph.add_node(*from);
+		}
+		for (_,from,next) in paths {
+			for (at,to) in next {
+				if graph.node(&to).is_none() {
+					return Err(at.error(Expected::Other("a node defined on a line of its own")));
+				}
+				graph.add_edge(from, to);
+			}
+		}
+		Ok((directions,graph))
+	})
+}
 
-	// build a HashMap of node -> (left,right)
+#[instrument(name = "parse", skip_all)]
+pub fn parse(input: &str) -> Network {
+	let (directions,graph) = try_parse(input).unwrap_or_else(|e| panic!("{e}"));
+	debug!(directions = directions.len(), nodes = graph.len(), "parsed");
+	(directions,graph)
+}
 
-	let mut paths:HashMap<Node,(Node,Node)> = HashMap::new();
+#[instrument(name = "solve", skip_all, fields(part = 1))]
+pub fn part1(network: &Network) -> String {
 
-	lines.for_each(|l| {
-		let node:Node  = l.get(00..=02).unwrap().into();
-		let left:Node  = l.get(07..=09).unwrap().into();
-		let right:Node = l.get(12..=14).unwrap().into();
-		paths.insert(node,(left,right));
-	});
+	let (directions,graph) = network;
 
 	let mut steps:usize = 0;
 
 	const START:Node = Node(['A','A','A']);
 	const END:Node   = Node(['Z','Z','Z']);
 
-	let mut current:Node = START;
+	let start = graph.node(&START).unwrap_or_else(|| panic!("there's no {START} node"));
+	let end = graph.node(&END).unwrap_or_else(|| panic!("there's no {END} node"));
+	// needed for the walk below to end, though not enough: the directions may still never lead there
+	assert!(graph.reaches(start, end), "{END} can't be reached from {START}");
 
-	for d in directions {
-		let node = &current;
+	let mut current = start;
 
-		if *node == END {
+	for d in directions.iter().cycle() {
+
+		if current == end {
 			break;
 		} else {
 			steps += 1;
 		}
-		let (left,right) = paths.get(node).unwrap();
 
-		match d {
-			'L' => current = *left,
-			'R' => current = *right,
+		let side = match d {
+			'L' => 0,
+			'R' => 1,
 			_   => unreachable!()
-		}
+		};
+		current = graph.successors(current)[side];
 	}
 
+	debug!(steps, "reached {END}");
 	steps.to_string()
 }
 
+pub fn solve(input: &str) -> String {
+	part1(&parse(input))
+}
+
 #[cfg(test)]
 mod tests {
-	use super::solve;
+	use super::{solve, try_parse};
 	use pretty_assertions::assert_str_eq;
 
 	#[test]
@@ -77,4 +132,12 @@ ZZZ = (ZZZ, ZZZ)
 		let actual = solve(input);
 		assert_str_eq!(actual, expected);
 	}
+
+	#[test]
+	fn test_undefined_nodes() {
+		let error = try_parse("L\n\nAAA = (ZZZ, CCC)\nZZZ = (ZZZ, ZZZ)\n").unwrap_err();
+		assert_str_eq!(error.to_string(), "line 3, column 13: expected a node defined on a line of its own, found \"CCC)\"");
+		let error = try_parse("L\n\nAAA = (ZZZ, ZZZ)\nZZZ = (ZZZ, ZZZ)\nAAA = (AAA, AAA)\n").unwrap_err();
+		assert_str_eq!(error.to_string(), "line 5, column 1: expected a node that isn't defined yet, found \"AAA = (AAA, AAA)\"");
+	}
 }
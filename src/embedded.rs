@@ -0,0 +1,84 @@
+// Inputs and the ledger, built into the binary with the embed feature,
+// so that `aoc verify` can run with nothing else on disk
+
+use std::path::Path;
+
+// shared with build.rs, which embeds what it selects
+#[cfg(test)]
+mod files;
+
+#[cfg(feature = "embed")]
+static FILES: &[(&str, &[u8])] = include!(concat!(env!("OUT_DIR"), "/embedded.rs"));
+
+#[cfg(not(feature = "embed"))]
+static FILES: &[(&str, &[u8])] = &[];
+
+/// An embedded file, by its path relative to the repository, like inputs/2023/5.txt
+pub fn get(path:&Path) -> Option<&'static [u8]> {
+	FILES.iter().find(|(name, _)| Path::new(name) == path).map(|(_, data)| *data)
+}
+
+#[cfg(test)]
+mod tests {
+	use std::{env, fs, path::PathBuf};
+	use pretty_assertions::assert_eq;
+	use crate::{config::{Config, File, Settings}, days::YEAR};
+	use super::*;
+
+	#[test]
+	fn test_dirs() {
+		// build.rs finds the directories where the configuration does at runtime
+		let file = r#"
+			profile = "ci"
+			inputs = "data"
+			[profiles.ci]
+			year = 2022
+			[profiles.local]
+			cache = "/var/cache/aoc"
+		"#;
+		let vars:[&[(&str,&str)];4] = [
+			&[],
+			&[("AOC_PROFILE", "local")],
+			&[("AOC_INPUTS", "elsewhere"), ("AOC_YEAR", "2021")],
+			&[("AOC_PROFILE", "local"), ("AOC_CACHE", "records")],
+		];
+		for vars in vars {
+			let var = |name:&str| vars.iter().find(|(n, _)| *n == name).map(|(_, v)| v.to_string());
+			let settings = File::parse(file).unwrap().settings(var("AOC_PROFILE").as_deref()).unwrap();
+			let config = Config::from(settings.or(Settings::from_env(var).unwrap()));
+			assert_eq!(files::dirs(Some(file), var, YEAR).unwrap(), [config.inputs_dir(), config.cache_dir()], "{vars:?}");
+		}
+		assert_eq!(files::dirs(None, |_| None, YEAR).unwrap(), [Config::default().inputs_dir(), Config::default().cache_dir()]);
+		assert!(files::dirs(Some("profile = \"missing\""), |_| None, YEAR).is_err());
+	}
+
+	#[test]
+	fn test_select() {
+		let root = env::temp_dir().join(format!("aoc-embedded-{}", std::process::id()));
+		let dirs = [Path::new("inputs/2023").to_owned(), Path::new("cache/2023").to_owned()];
+		for dir in &dirs {
+			fs::create_dir_all(root.join(dir)).unwrap();
+		}
+		for file in ["inputs/2023/1.txt", "inputs/2023/1.txt.enc", "inputs/2023/2.txt.enc", "inputs/2023/notes.md", "cache/2023/ledger.json.enc", "cache/2023/timings.json"] {
+			fs::write(root.join(file), "").unwrap();
+		}
+		let selected = files::select(&root, &dirs);
+		fs::remove_dir_all(&root).unwrap();
+		assert_eq!(selected, ["inputs/2023/1.txt", "inputs/2023/2.txt.enc", "cache/2023/ledger.json.enc"].map(PathBuf::from));
+	}
+
+	#[cfg(feature = "embed")]
+	#[test]
+	fn test_embedded() {
+		// what was built in is what's there, by the paths the configuration reads them from
+		let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+		let file = fs::read_to_string(root.join(env::var("AOC_CONFIG").unwrap_or_else(|_| "aoc.toml".into()))).ok();
+		let dirs = files::dirs(file.as_deref(), |var| env::var(var).ok(), YEAR).unwrap();
+		let selected = files::select(root, &dirs);
+		assert_eq!(FILES.iter().map(|(name, _)| PathBuf::from(name)).collect::<Vec<_>>(), selected);
+		for (name, data) in FILES {
+			assert_eq!(get(Path::new(name)), Some(*data));
+			assert_eq!(fs::read(root.join(name)).unwrap(), *data);
+		}
+	}
+}
@@ -0,0 +1,51 @@
+// The files to build into the binary, found where the configuration puts them at runtime.
+// Shared with build.rs, so it only uses std and toml.
+
+use std::{fs, path::{Path, PathBuf}};
+
+/// The inputs and the cache directories of the configured year, as `Config` finds them:
+/// the configuration file, with its profile on top, then AOC_* variables
+pub fn dirs(file:Option<&str>, var:impl Fn(&str) -> Option<String>, default_year:u16) -> Result<[PathBuf;2],String> {
+
+	let table:toml::Table = file.unwrap_or_default().parse().map_err(|e:toml::de::Error| e.to_string())?;
+	let profile = var("AOC_PROFILE").or_else(|| table.get("profile")?.as_str().map(String::from));
+	let profile = match profile {
+		None => None,
+		Some(name) => Some(
+			table.get("profiles").and_then(|p| p.get(&name)).ok_or(format!("there is no profile {name}"))?
+		),
+	};
+
+	// the variable, else the profile, else the file
+	let setting = |key:&str, name:&str| var(name).or_else(|| {
+		let value = profile.and_then(|p| p.get(key)).or(table.get(key))?;
+		Some(value.as_str().map(String::from).unwrap_or_else(|| value.to_string()))
+	});
+
+	let year = setting("year", "AOC_YEAR").map_or(Ok(default_year), |y| y.parse().map_err(|_| format!("year {y} is not valid")))?;
+	let inputs = setting("inputs", "AOC_INPUTS").unwrap_or_else(|| "inputs".into());
+	let cache = setting("cache", "AOC_CACHE").unwrap_or_else(|| "cache".into());
+	Ok([inputs, cache].map(|dir| Path::new(&dir).join(year.to_string())))
+}
+
+/// The inputs and the ledger in the directories, as they are, or encrypted when that's all there is,
+/// by their path under `root`
+pub fn select(root:&Path, dirs:&[PathBuf]) -> Vec<PathBuf> {
+	let mut files = vec![];
+	for dir in dirs {
+		let Ok(entries) = fs::read_dir(root.join(dir)) else {
+			continue;
+		};
+		let mut names:Vec<String> = entries.flatten().filter_map(|e| e.file_name().into_string().ok()).collect();
+		names.sort();
+		for name in &names {
+			let plain = name.strip_suffix(".enc").unwrap_or(name);
+			let wanted = plain.ends_with(".txt") || plain == "ledger.json";
+			// the encrypted version is only needed when the plain one isn't there
+			if wanted && (plain == name || !names.iter().any(|n| n == plain)) {
+				files.push(dir.join(name));
+			}
+		}
+	}
+	files
+}
@@ -0,0 +1,58 @@
+use std::{fmt, io};
+
+use crate::{input::Rejected, ledger::{Refusal, Verdict}};
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+#[derive(Debug)]
+pub enum Error {
+	/// a downloaded input that doesn't look like one
+	Input(Rejected),
+	Http(Box<ureq::Error>),
+	Io(io::Error),
+	/// the submitted answer was wrong
+	Wrong(Verdict),
+	/// the answer wasn't submitted, since it is known to be wrong
+	Refused(Refusal),
+	/// the part was already complete, so the answer wasn't checked
+	Unverified(String),
+	/// with how long is left to wait
+	RateLimited(String),
+	UnexpectedResponse,
+	/// the solver panicked, with this message
+	Panic(String),
+}
+
+impl fmt::Display for Error {
+	fn fmt(&self, f:&mut fmt::Formatter) -> fmt::Result {
+		match self {
+			Error::Input(rejected) => write!(f, "rejected input download: {rejected}"),
+			Error::Http(e) => write!(f, "request failed: {e}"),
+			Error::Io(e) => write!(f, "{e}"),
+			Error::Wrong(verdict) => write!(f, "the answer was {verdict}"),
+			Error::Refused(refusal) => write!(f, "not submitting: {refusal}"),
+			Error::Unverified(answer) => write!(f, "the part was already complete, so {answer} wasn't checked, it's in the ledger as unverified"),
+			Error::RateLimited(wait) => write!(f, "rate limited, wait {wait}"),
+			Error::UnexpectedResponse => write!(f, "unexpected response to the submission"),
+			Error::Panic(msg) => write!(f, "the solver panicked: {msg}"),
+		}
+	}
+}
+
+impl std::error::Error for Error {}
+
+impl From<Rejected> for Error {
+	fn from(rejected:Rejected) -> Self { Error::Input(rejected) }
+}
+
+impl From<ureq::Error> for Error {
+	fn from(e:ureq::Error) -> Self { Error::Http(Box::new(e)) }
+}
+
+impl From<io::Error> for Error {
+	fn from(e:io::Error) -> Self { Error::Io(e) }
+}
+
+impl From<Refusal> for Error {
+	fn from(refusal:Refusal) -> Self { Error::Refused(refusal) }
+}
@@ -0,0 +1,186 @@
+// C interface to the solvers, see include/aoc.h
+
+use std::{cell::{Cell, RefCell}, ffi::{c_char, CStr, CString}, panic, ptr, slice, str, sync::Once};
+
+use crate::{days::{self, YEAR}, error::Error, runner};
+
+/// What aoc_solve did. Panics never cross the boundary, they are reported as one of these.
+#[repr(C)]
+#[derive(Debug,Clone,Copy,PartialEq,Eq)]
+pub enum AocStatus {
+	Ok = 0,
+	NullPointer = 1,
+	InvalidUtf8 = 2,
+	NoSolver = 3,
+	SolverPanicked = 4,
+	BufferTooSmall = 5,
+}
+
+thread_local! {
+	// whether this thread is in a solver, whose panics the hook leaves to aoc_status_message
+	static SOLVING:Cell<bool> = const { Cell::new(false) };
+	// what the last solver to panic on this thread said
+	static PANIC:RefCell<Option<CString>> = const { RefCell::new(None) };
+}
+
+// the host's panic hook stays, but isn't called for the panics of solvers, which are caught anyway
+fn quiet_panics() {
+	static HOOK:Once = Once::new();
+	HOOK.call_once(|| {
+		let original = panic::take_hook();
+		panic::set_hook(Box::new(move |info| {
+			if !SOLVING.get() {
+				original(info)
+			}
+		}));
+	});
+}
+
+/// Solve a puzzle part's input, writing the answer as a NUL terminated string to `out`.
+///
+/// On success, and when `out` is too small, `out_len` (unless NULL) is set to the answer's length,
+/// not counting the NUL, so a buffer of `out_len + 1` bytes will do.
+/// A solver's panic isn't printed; aoc_status_message gives its message instead.
+///
+/// # Safety
+///
+/// `input` must point to `input_len` readable bytes, and `out` to `out_cap` writable ones.
+#[no_mangle]
+pub unsafe extern "C" fn aoc_solve(
+	year: u32,
+	day: u32,
+	part: u32,
+	input: *const u8,
+	input_len: usize,
+	out: *mut c_char,
+	out_cap: usize,
+	out_len: *mut usize,
+) -> AocStatus {
+
+	if input.is_null() || out.is_null() {
+		return AocStatus::NullPointer;
+	}
+	let Ok(input) = str::from_utf8(slice::from_raw_parts(input, input_len)) else {
+		return AocStatus::InvalidUtf8;
+	};
+
+	let solver = u8::try_from(day).ok()
+		.zip(u8::try_from(part).ok())
+		.filter(|_| year == u32::from(YEAR))
+		.and_then(|(day,part)| days::solver(day, part));
+	let Some(solver) = solver else {
+		return AocStatus::NoSolver;
+	};
+
+	quiet_panics();
+	SOLVING.set(true);
+	let result = panic::catch_unwind(|| runner::solve(solver, input));
+	SOLVING.set(false);
+	let run = match result {
+		Ok(Ok(run)) => run,
+		Ok(Err(e)) => return panicked(&e),
+		Err(payload) => return panicked(&Error::Panic(runner::panic_message(payload))),
+	};
+	PANIC.take();
+
+	let answer = run.answer.as_bytes();
+	if !out_len.is_null() {
+		*out_len = answer.len();
+	}
+	if answer.len() >= out_cap {
+		return AocStatus::BufferTooSmall;
+	}
+	ptr::copy_nonoverlapping(answer.as_ptr(), out.cast::<u8>(), answer.len());
+	*out.add(answer.len()) = 0;
+	AocStatus::Ok
+}
+
+fn panicked(e:&Error) -> AocStatus {
+	let message = CString::new(e.to_string().replace('\0', "")).unwrap();
+	PANIC.set(Some(message));
+	AocStatus::SolverPanicked
+}
+
+impl AocStatus {
+	const ALL:[AocStatus;6] = [
+		AocStatus::Ok,
+		AocStatus::NullPointer,
+		AocStatus::InvalidUtf8,
+		AocStatus::NoSolver,
+		AocStatus::SolverPanicked,
+		AocStatus::BufferTooSmall,
+	];
+}
+
+/// A description of a status code, "unknown status" for codes that aren't an AocStatus.
+///
+/// For AOC_STATUS_SOLVER_PANICKED, it's what the solver panicked with, if the last aoc_solve
+/// on this thread was the one that panicked; that string lasts until the next aoc_solve on this
+/// thread. The other descriptions are static.
+#[no_mangle]
+pub extern "C" fn aoc_status_message(status: u32) -> *const c_char {
+	if status == AocStatus::SolverPanicked as u32 {
+		if let Some(message) = PANIC.with_borrow(|p| p.as_ref().map(|m| m.as_ptr())) {
+			return message;
+		}
+	}
+	// an integer from C, not trusted to be one of the enum's values
+	let msg:&CStr = match AocStatus::ALL.into_iter().find(|&s| s as u32 == status) {
+		Some(AocStatus::Ok) => c"ok",
+		Some(AocStatus::NullPointer) => c"a pointer argument was NULL",
+		Some(AocStatus::InvalidUtf8) => c"the input is not valid UTF-8",
+		Some(AocStatus::NoSolver) => c"there is no solver for that puzzle part",
+		Some(AocStatus::SolverPanicked) => c"the solver failed, the input may be malformed",
+		Some(AocStatus::BufferTooSmall) => c"the output buffer is too small for the answer",
+		None => c"unknown status",
+	};
+	msg.as_ptr()
+}
+
+#[cfg(test)]
+mod tests {
+	use pretty_assertions::assert_eq;
+	use super::*;
+
+	fn solve(year:u32, day:u32, part:u32, input:&str, cap:usize) -> (AocStatus, String, usize) {
+		let mut out = vec![0 as c_char; cap.max(1)];
+		let mut len = 0;
+		let status = unsafe { aoc_solve(year, day, part, input.as_ptr(), input.len(), out.as_mut_ptr(), cap, &mut len) };
+		let answer = unsafe { CStr::from_ptr(out.as_ptr()) }.to_str().unwrap().to_string();
+		(status, answer, len)
+	}
+
+	#[test]
+	fn test_aoc_solve() {
+		let input = "Time:      7  15   30\nDistance:  9  40  200\n";
+		assert_eq!(solve(2023, 6, 1, input, 16), (AocStatus::Ok, "288".into(), 3));
+		assert_eq!(solve(2023, 6, 1, input, 3), (AocStatus::BufferTooSmall, "".into(), 3));
+		assert_eq!(solve(2023, 6, 1, "bad", 16).0, AocStatus::SolverPanicked);
+		assert_eq!(solve(2023, 25, 2, input, 16).0, AocStatus::NoSolver);
+		assert_eq!(solve(2022, 6, 1, input, 16).0, AocStatus::NoSolver);
+
+		let status = unsafe { aoc_solve(2023, 6, 1, ptr::null(), 0, ptr::null_mut(), 0, ptr::null_mut()) };
+		assert_eq!(status, AocStatus::NullPointer);
+		let bytes = [0xff_u8, 0xfe];
+		let mut out = [0 as c_char; 8];
+		let status = unsafe { aoc_solve(2023, 6, 1, bytes.as_ptr(), bytes.len(), out.as_mut_ptr(), out.len(), ptr::null_mut()) };
+		assert_eq!(status, AocStatus::InvalidUtf8);
+	}
+
+	#[test]
+	fn test_aoc_status_message() {
+		let message = |status:AocStatus| unsafe { CStr::from_ptr(aoc_status_message(status as u32)) }.to_str().unwrap().to_string();
+		assert_eq!(message(AocStatus::NoSolver), "there is no solver for that puzzle part");
+		assert_eq!(message(AocStatus::SolverPanicked), "the solver failed, the input may be malformed");
+		let unknown = |status| unsafe { CStr::from_ptr(aoc_status_message(status)) }.to_str().unwrap();
+		assert_eq!(unknown(6), "unknown status");
+		assert_eq!(unknown(u32::MAX), "unknown status");
+
+		// until the next call, on this thread, the panic's own message
+		assert_eq!(solve(2023, 6, 1, "bad", 16).0, AocStatus::SolverPanicked);
+		assert_eq!(message(AocStatus::SolverPanicked), "the solver panicked: line 1, column 1: expected \"Time\", found \"bad\"");
+		assert!(!SOLVING.get());
+		assert_eq!(solve(2023, 6, 1, "Time: 7\nDistance: 9\n", 16).0, AocStatus::Ok);
+		assert_eq!(message(AocStatus::SolverPanicked), "the solver failed, the input may be malformed");
+	}
+}
@@ -0,0 +1,130 @@
+// Puzzle inputs: downloading, checking and caching them
+
+use std::{fmt, fs, io, path::PathBuf};
+
+use crate::{config, error::Result, vault};
+
+/// Why a download was not taken as a puzzle input
+#[derive(Debug,Clone,PartialEq,Eq)]
+pub enum Rejected {
+	Empty,
+	Html,
+	LoggedOut,
+	NoTrailingNewline,
+	/// it doesn't start the way the day's inputs do
+	Shape { day: u8, expected: &'static str },
+}
+
+impl fmt::Display for Rejected {
+	fn fmt(&self, f:&mut fmt::Formatter) -> fmt::Result {
+		match self {
+			Rejected::Empty => write!(f, "it is empty"),
+			Rejected::Html => write!(f, "it is an HTML page, the session cookie has probably expired"),
+			Rejected::LoggedOut => write!(f, "it asks to log in, the session cookie has probably expired"),
+			Rejected::NoTrailingNewline => write!(f, "it doesn't end with a newline, so it may be truncated"),
+			Rejected::Shape { day, expected } => write!(f, "it doesn't look like a day {day} input, which starts with {expected}"),
+		}
+	}
+}
+
+// how the first line of a day's input looks, for the days it is distinctive
+struct Shape {
+	day: u8,
+	expected: &'static str,
+	matches: fn(&str) -> bool,
+}
+
+static SHAPES:&[Shape] = &[
+	Shape { day: 2, expected: "a \"Game <n>:\" line", matches: |l| l.starts_with("Game ") },
+	Shape { day: 4, expected: "a \"Card <n>:\" line", matches: |l| l.starts_with("Card ") },
+	Shape { day: 5, expected: "a \"seeds:\" header", matches: |l| l.starts_with("seeds:") },
+	Shape { day: 6, expected: "a \"Time:\" line", matches: |l| l.starts_with("Time:") },
+	Shape { day: 8, expected: "a line of L/R directions", matches: |l| !l.is_empty() && l.chars().all(|c| c == 'L' || c == 'R') },
+];
+
+/// Check a download really is a day's puzzle input
+pub fn check(day:u8, input:&str) -> std::result::Result<(), Rejected> {
+
+	// the first characters, however short or multibyte the text is
+	let text = input.trim_start();
+	let head = text.char_indices().nth(15).map_or(text, |(i, _)| &text[..i]).to_ascii_lowercase();
+
+	if input.trim().is_empty() {
+		Err(Rejected::Empty)
+	} else if head.starts_with("<!doctype") || head.starts_with("<html") {
+		Err(Rejected::Html)
+	} else if input.contains("Please log in") {
+		Err(Rejected::LoggedOut)
+	} else if !input.ends_with('\n') {
+		Err(Rejected::NoTrailingNewline)
+	} else {
+		let first = input.lines().next().unwrap_or_default();
+		match SHAPES.iter().find(|s| s.day == day) {
+			Some(shape) if !(shape.matches)(first) => Err(Rejected::Shape { day, expected: shape.expected }),
+			_ => Ok(())
+		}
+	}
+}
+
+pub fn path(day:u8) -> PathBuf {
+	config::get().inputs_dir().join(format!("{day}.txt"))
+}
+
+/// Download a day's input, as is
+pub fn download(session:&str, day:u8) -> Result<String> {
+	let config = config::get();
+	let url = format!("https://adventofcode.com/{}/day/{day}/input", config.year);
+	let response = config.agent().get(&url)
+		.set("Cookie", &format!("session={session}"))
+		.call();
+	match response {
+		Ok(response) => Ok(response.into_string()?),
+		// that's how an expired session is answered
+		Err(ureq::Error::Status(400, _)) => Err(Rejected::LoggedOut.into()),
+		Err(e) => Err(e.into()),
+	}
+}
+
+/// The cached input for a day, decrypting it if only its encrypted version is there
+pub fn cached(day:u8) -> io::Result<String> {
+	vault::read_to_string(&path(day))
+}
+
+/// The cached input for a day, downloading (and checking) it first if needed
+pub fn load(session:&str, day:u8) -> Result<String> {
+	let path = path(day);
+	match cached(day) {
+		Ok(input) => return Ok(input),
+		Err(e) if e.kind() != io::ErrorKind::NotFound => return Err(e.into()),
+		Err(_) => {}
+	}
+	let input = download(session, day)?;
+	check(day, &input)?;
+	if let Some(dir) = path.parent() {
+		fs::create_dir_all(dir)?;
+	}
+	fs::write(&path, &input)?;
+	Ok(input)
+}
+
+#[cfg(test)]
+mod tests {
+	use pretty_assertions::assert_eq;
+	use super::*;
+
+	#[test]
+	fn test_check() {
+		assert_eq!(check(5, "seeds: 79 14 55 13\n\nseed-to-soil map:\n50 98 2\n"), Ok(()));
+		assert_eq!(check(8, "LLR\n\nAAA = (BBB, BBB)\n"), Ok(()));
+		assert_eq!(check(1, "1abc2\n"), Ok(()));
+		assert_eq!(check(5, ""), Err(Rejected::Empty));
+		assert_eq!(check(5, "<!DOCTYPE html>\n<html lang=\"en-us\">\n"), Err(Rejected::Html));
+		// short, or with a multibyte character where a byte prefix would end
+		assert_eq!(check(5, "\n<html>\n"), Err(Rejected::Html));
+		assert_eq!(check(5, "  <html><title>xé</title>\n"), Err(Rejected::Html));
+		assert_eq!(check(5, "Puzzle inputs differ by user.  Please log in to get your puzzle input.\n"), Err(Rejected::LoggedOut));
+		assert_eq!(check(5, "seeds: 79 14 55 13"), Err(Rejected::NoTrailingNewline));
+		assert!(matches!(check(5, "Game 1: 3 blue\n"), Err(Rejected::Shape { day: 5, .. })));
+		assert!(matches!(check(8, "AAA = (BBB, BBB)\n"), Err(Rejected::Shape { day: 8, .. })));
+	}
+}
@@ -0,0 +1,159 @@
+// Private leaderboards: who got which stars, and how long it took them
+
+use std::{collections::HashMap, fs, io, path::Path, time::{Duration, UNIX_EPOCH}};
+use serde::Deserialize;
+
+use crate::{
+	config,
+	error::Result,
+	ledger::Ledger,
+	output::Table,
+	schedule::unlock_time,
+};
+
+#[derive(Deserialize,Debug)]
+pub struct Leaderboard {
+	pub event: String,
+	pub members: HashMap<String,Member>,
+}
+
+#[derive(Deserialize,Debug)]
+pub struct Member {
+	pub id: u64,
+	/// anonymous users have none
+	pub name: Option<String>,
+	pub stars: u32,
+	pub local_score: u32,
+	/// day -> part -> star
+	pub completion_day_level: HashMap<String,HashMap<String,Star>>,
+}
+
+#[derive(Deserialize,Debug)]
+pub struct Star {
+	/// seconds since the unix epoch
+	pub get_star_ts: u64,
+}
+
+impl Leaderboard {
+
+	/// A leaderboard of the configured year, others would give nonsense times since unlock
+	pub fn parse(json:&str) -> Result<Self> {
+		let leaderboard:Leaderboard = serde_json::from_str(json).map_err(io::Error::other)?;
+		let year = config::get().year;
+		if leaderboard.event != year.to_string() {
+			let msg = format!("the leaderboard is for {}, not {year}", leaderboard.event);
+			return Err(io::Error::new(io::ErrorKind::InvalidData, msg).into());
+		}
+		Ok(leaderboard)
+	}
+
+	pub fn load(path:&Path) -> Result<Self> {
+		Self::parse(&fs::read_to_string(path)?)
+	}
+
+	/// Fetch a leaderboard's JSON, from AoC or a stand-in for it
+	pub fn fetch(url:&str, session:Option<&str>) -> Result<Self> {
+		let mut request = config::get().agent().get(url);
+		if let Some(session) = session {
+			request = request.set("Cookie", &format!("session={session}"));
+		}
+		Self::parse(&request.call()?.into_string()?)
+	}
+}
+
+impl Member {
+	pub fn display_name(&self) -> String {
+		self.name.clone().unwrap_or_else(|| format!("(anonymous user #{})", self.id))
+	}
+}
+
+// time from a day's unlock until a star was got
+fn completion(day:u8, at:u64) -> Duration {
+	(UNIX_EPOCH + Duration::from_secs(at)).duration_since(unlock_time(day)).unwrap_or_default()
+}
+
+/// One row per member and day with stars: when each part was completed, and the time between them.
+/// The solvers in this crate are the "me" row, from the answers the ledger has accepted.
+pub fn analyze(leaderboard:&Leaderboard, ledger:&Ledger) -> Table {
+
+	let mut table = Table::new(&["member", "day", "stars", "part1", "part2", "delta"]);
+	let mut push = |member:String, day:u8, parts:[Option<Duration>;2]| {
+		if let [None, None] = parts {
+			return;
+		}
+		let [part1, part2] = parts;
+		let delta = part1.zip(part2).map(|(p1,p2)| p2.saturating_sub(p1));
+		let stars = u8::try_from(parts.iter().flatten().count()).unwrap();
+		table.push(vec![member.into(), day.into(), stars.into(), part1.into(), part2.into(), delta.into()]);
+	};
+
+	let mut members:Vec<&Member> = leaderboard.members.values().collect();
+	members.sort_by_key(|m| (std::cmp::Reverse(m.local_score), m.id));
+
+	for member in members {
+		for day in 1..=25 {
+			let parts = ["1","2"].map(|part| {
+				member.completion_day_level
+					.get(&day.to_string())
+					.and_then(|parts| parts.get(part))
+					.map(|star| completion(day, star.get_star_ts))
+			});
+			push(member.display_name(), day, parts);
+		}
+	}
+
+	for day in 1..=25 {
+		let parts = [1,2].map(|part| {
+			ledger.submissions
+				.iter()
+				.find(|s| s.day == day && s.part == part && ledger.accepted(day, part) == Some(&s.answer))
+				.map(|s| completion(day, s.at))
+		});
+		push("me".into(), day, parts);
+	}
+
+	table
+}
+
+#[cfg(test)]
+mod tests {
+	use pretty_assertions::assert_str_eq;
+	use crate::{ledger::{Submission, Verdict}, output::Format};
+	use super::*;
+
+	#[test]
+	fn test_analyze() {
+		// day 1 unlocked at 1701406800
+		let json = r#"{
+			"event": "2023",
+			"owner_id": 1,
+			"members": {
+				"1": {
+					"id": 1, "name": "Ada", "stars": 3, "local_score": 10, "last_star_ts": 1701500000,
+					"completion_day_level": {
+						"1": { "1": { "get_star_ts": 1701407400, "star_index": 1 }, "2": { "get_star_ts": 1701411000, "star_index": 2 } },
+						"2": { "1": { "get_star_ts": 1701500000, "star_index": 3 } }
+					}
+				},
+				"2": { "id": 2, "name": null, "stars": 0, "local_score": 0, "last_star_ts": 0, "completion_day_level": {} }
+			}
+		}"#;
+		let leaderboard = Leaderboard::parse(json).unwrap();
+
+		let mut ledger = Ledger::default();
+		ledger.record(Submission { day: 1, part: 1, answer: "1".into(), verdict: Verdict::TooLow, at: 1701406860 });
+		ledger.record(Submission { day: 1, part: 1, answer: "2".into(), verdict: Verdict::Correct, at: 1701406920 });
+
+		let table = analyze(&leaderboard, &ledger);
+		assert_str_eq!(table.render(Format::Text), "\
+member  day  stars  part1    part2    delta
+Ada     1    2      0:10:00  1:10:00  1:00:00
+Ada     2    1      1:53:20
+me      1    1      0:02:00
+");
+		assert_str_eq!(table.render(Format::Csv).lines().nth(1).unwrap(), "Ada,1,2,600,4200,3600");
+
+		let error = Leaderboard::parse(&json.replace(r#""event": "2023""#, r#""event": "2022""#)).unwrap_err();
+		assert_str_eq!(error.to_string(), "the leaderboard is for 2022, not 2023");
+	}
+}
@@ -0,0 +1,190 @@
+// Every answer submitted, with the verdict it got
+
+use std::{fmt, io, path::{Path, PathBuf}};
+use serde::{Deserialize, Serialize};
+
+use crate::{config, store};
+
+#[derive(Serialize,Deserialize,Debug,Clone,Copy,PartialEq,Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum Verdict {
+	Correct,
+	Incorrect,
+	TooHigh,
+	TooLow,
+	/// posted after the part was completed some other way, so AoC didn't check it
+	Unverified,
+}
+
+impl fmt::Display for Verdict {
+	fn fmt(&self, f:&mut fmt::Formatter) -> fmt::Result {
+		match self {
+			Verdict::Correct => write!(f, "correct"),
+			Verdict::Incorrect => write!(f, "incorrect"),
+			Verdict::TooHigh => write!(f, "too high"),
+			Verdict::TooLow => write!(f, "too low"),
+			Verdict::Unverified => write!(f, "unverified"),
+		}
+	}
+}
+
+/// Why an answer won't be submitted
+#[derive(Debug,Clone,PartialEq,Eq)]
+pub enum Refusal {
+	AlreadyRejected { answer: String, verdict: Verdict },
+	/// another answer was accepted already
+	NotAccepted { answer: String, accepted: String },
+	/// it is at least as high as an answer that was too high
+	TooHigh { answer: i64, bound: i64 },
+	/// it is at most as low as an answer that was too low
+	TooLow { answer: i64, bound: i64 },
+}
+
+impl fmt::Display for Refusal {
+	fn fmt(&self, f:&mut fmt::Formatter) -> fmt::Result {
+		match self {
+			Refusal::AlreadyRejected { answer, verdict } => write!(f, "{answer} was already submitted, and was {verdict}"),
+			Refusal::NotAccepted { answer, accepted } => write!(f, "{answer} is wrong, {accepted} was the accepted answer"),
+			Refusal::TooHigh { answer, bound } => write!(f, "{answer} is too high, since {bound} already was"),
+			Refusal::TooLow { answer, bound } => write!(f, "{answer} is too low, since {bound} already was"),
+		}
+	}
+}
+
+#[derive(Serialize,Deserialize,Debug,Clone,PartialEq,Eq)]
+pub struct Submission {
+	pub day: u8,
+	pub part: u8,
+	pub answer: String,
+	pub verdict: Verdict,
+	/// seconds since the unix epoch
+	pub at: u64,
+}
+
+#[derive(Serialize,Deserialize,Debug,Default)]
+pub struct Ledger {
+	pub submissions: Vec<Submission>,
+}
+
+impl Ledger {
+
+	pub fn path() -> PathBuf {
+		config::get().cache_dir().join("ledger.json")
+	}
+
+	pub fn load(path:&Path) -> io::Result<Self> {
+		store::load(path)
+	}
+
+	pub fn save(&self, path:&Path) -> io::Result<()> {
+		store::save(path, self)
+	}
+
+	pub fn record(&mut self, submission:Submission) {
+		self.submissions.push(submission);
+	}
+
+	/// The answer accepted for a puzzle part, if any
+	pub fn accepted(&self, day:u8, part:u8) -> Option<&str> {
+		self.submissions
+			.iter()
+			.find(|s| s.day == day && s.part == part && s.verdict == Verdict::Correct)
+			.map(|s| s.answer.as_str())
+	}
+
+	/// Whether this answer was posted before, but not checked
+	pub fn unverified(&self, day:u8, part:u8, answer:&str) -> bool {
+		self.submissions.iter().any(|s| s.day == day && s.part == part && s.answer == answer && s.verdict == Verdict::Unverified)
+	}
+
+	/// The exclusive range a numeric answer must be in, from those that were too low or too high
+	pub fn bounds(&self, day:u8, part:u8) -> (Option<i64>,Option<i64>) {
+		let rejected = |verdict| self.submissions
+			.iter()
+			.filter(move |s| s.day == day && s.part == part && s.verdict == verdict)
+			.filter_map(|s| s.answer.parse::<i64>().ok());
+		(rejected(Verdict::TooLow).max(), rejected(Verdict::TooHigh).min())
+	}
+
+	/// Whether an answer is worth submitting, given what was submitted before
+	pub fn check(&self, day:u8, part:u8, answer:&str) -> Result<(),Refusal> {
+
+		if let Some(accepted) = self.accepted(day, part) {
+			return if accepted == answer {
+				Ok(())
+			} else {
+				Err(Refusal::NotAccepted { answer: answer.into(), accepted: accepted.into() })
+			};
+		}
+
+		let rejected = |s:&&Submission| s.day == day && s.part == part && s.answer == answer && s.verdict != Verdict::Unverified;
+		if let Some(s) = self.submissions.iter().find(rejected) {
+			return Err(Refusal::AlreadyRejected { answer: answer.into(), verdict: s.verdict });
+		}
+
+		if let Ok(n) = answer.parse::<i64>() {
+			match self.bounds(day, part) {
+				(Some(low),_) if n <= low => return Err(Refusal::TooLow { answer: n, bound: low }),
+				(_,Some(high)) if n >= high => return Err(Refusal::TooHigh { answer: n, bound: high }),
+				_ => {}
+			}
+		}
+
+		Ok(())
+	}
+
+	pub fn stars(&self, day:u8) -> usize {
+		[1,2].into_iter().filter(|&part| self.accepted(day, part).is_some()).count()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use pretty_assertions::assert_eq;
+	use super::*;
+
+	#[test]
+	fn test_stars() {
+		let submission = |part, answer:&str, verdict| Submission { day: 3, part, answer: answer.into(), verdict, at: 0 };
+		let mut ledger = Ledger::default();
+		ledger.record(submission(1, "41", Verdict::Incorrect));
+		ledger.record(submission(1, "42", Verdict::Correct));
+		ledger.record(submission(2, "7", Verdict::Incorrect));
+		assert_eq!(ledger.accepted(3, 1), Some("42"));
+		assert_eq!(ledger.accepted(3, 2), None);
+		assert_eq!(ledger.stars(3), 1);
+		assert_eq!(ledger.stars(4), 0);
+	}
+
+	#[test]
+	fn test_check() {
+		let submission = |answer:&str, verdict| Submission { day: 5, part: 1, answer: answer.into(), verdict, at: 0 };
+		let mut ledger = Ledger::default();
+		ledger.record(submission("100", Verdict::TooLow));
+		ledger.record(submission("250", Verdict::TooLow));
+		ledger.record(submission("900", Verdict::TooHigh));
+		ledger.record(submission("500", Verdict::Incorrect));
+		ledger.record(submission("abc", Verdict::Incorrect));
+
+		assert_eq!(ledger.bounds(5, 1), (Some(250), Some(900)));
+		assert_eq!(ledger.bounds(5, 2), (None, None));
+
+		assert_eq!(ledger.check(5, 1, "251"), Ok(()));
+		assert_eq!(ledger.check(5, 1, "899"), Ok(()));
+		assert_eq!(ledger.check(5, 1, "xyz"), Ok(()));
+		assert_eq!(ledger.check(5, 1, "500"), Err(Refusal::AlreadyRejected { answer: "500".into(), verdict: Verdict::Incorrect }));
+		assert_eq!(ledger.check(5, 1, "abc"), Err(Refusal::AlreadyRejected { answer: "abc".into(), verdict: Verdict::Incorrect }));
+		assert_eq!(ledger.check(5, 1, "200"), Err(Refusal::TooLow { answer: 200, bound: 250 }));
+		assert_eq!(ledger.check(5, 1, "900"), Err(Refusal::AlreadyRejected { answer: "900".into(), verdict: Verdict::TooHigh }));
+		assert_eq!(ledger.check(5, 1, "1000"), Err(Refusal::TooHigh { answer: 1000, bound: 900 }));
+
+		// an answer that wasn't checked isn't known to be wrong
+		ledger.record(submission("400", Verdict::Unverified));
+		assert!(ledger.unverified(5, 1, "400"));
+		assert_eq!(ledger.check(5, 1, "400"), Ok(()));
+
+		ledger.record(submission("300", Verdict::Correct));
+		assert_eq!(ledger.check(5, 1, "300"), Ok(()));
+		assert_eq!(ledger.check(5, 1, "301"), Err(Refusal::NotAccepted { answer: "301".into(), accepted: "300".into() }));
+	}
+}
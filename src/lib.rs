@@ -0,0 +1,22 @@
+pub mod config;
+pub mod days;
+pub mod dashboard;
+pub mod embedded;
+pub mod error;
+pub mod ffi;
+pub mod input;
+pub mod leaderboard;
+pub mod ledger;
+pub mod output;
+#[cfg(feature = "python")]
+mod python;
+pub mod report;
+pub mod runner;
+pub mod schedule;
+pub mod serve;
+pub mod store;
+pub mod submit;
+pub mod timings;
+pub mod trace;
+pub mod utils;
+pub mod vault;
@@ -1,19 +1,300 @@
-use std::{env, error::Error};
-use aoc_driver::*;
-
-mod days;
-use days::*;
-
-fn main()->Result<(),Box<dyn Error>> {
-	let cookie : String = env::var("COOKIE")?;
-	aoc_magic!(&cookie, 2023:1:1, day01::solve)?;
-	aoc_magic!(&cookie, 2023:2:1, day02::solve)?;
-	aoc_magic!(&cookie, 2023:3:1, day03::solve)?;
-	aoc_magic!(&cookie, 2023:4:1, day04::solve)?;
-	aoc_magic!(&cookie, 2023:5:1, day05::solve)?;
-	aoc_magic!(&cookie, 2023:6:1, day06::solve)?;
-	aoc_magic!(&cookie, 2023:7:1, day07::solve)?;
-	aoc_magic!(&cookie, 2023:8:1, day08::solve)?;
-	println!("All done!");
+use std::{env, error::Error, fs, io::{self, Write}, path::{Path, PathBuf}, process::ExitCode};
+use clap::{Parser, Subcommand};
+
+use advent_of_code_2023::{
+	config::{self, Config, Settings, Submit},
+	dashboard,
+	days::{self, Solver, SOLVERS},
+	input,
+	leaderboard::{self, Leaderboard},
+	ledger::Ledger,
+	output::{Format, Table},
+	report,
+	runner::{self, Check, Run},
+	schedule::{self, SystemClock},
+	serve,
+	timings::Timings,
+	trace,
+	vault::{self, Key},
+};
+
+#[derive(Parser)]
+#[command(name = "aoc", about = "Advent of Code 2023 solutions")]
+struct Cli {
+	/// Trace the fetch, parse and solve phases: as a span tree on stderr,
+	/// or as a Chrome trace JSON when given a file, as --trace=FILE
+	#[arg(long, global = true, value_name = "FILE", num_args = 0..=1, require_equals = true, default_missing_value = "-")]
+	trace: Option<PathBuf>,
+	/// The configuration file, with settings that AOC_* variables and these options override
+	#[arg(long, global = true, env = "AOC_CONFIG", default_value = config::FILE)]
+	config: PathBuf,
+	/// A profile from the configuration file, to put on top of its settings
+	#[arg(long, global = true, env = "AOC_PROFILE")]
+	profile: Option<String>,
+	/// The AoC event to fetch inputs and keep records for
+	#[arg(long, global = true)]
+	year: Option<u16>,
+	/// Where inputs are kept, in a directory per year
+	#[arg(long, global = true, value_name = "DIR")]
+	inputs: Option<PathBuf>,
+	/// Where the ledger and timings are kept, in a directory per year
+	#[arg(long, global = true, value_name = "DIR")]
+	cache: Option<PathBuf>,
+	/// When answers get submitted
+	#[arg(long, global = true, value_enum)]
+	submit: Option<Submit>,
+	/// For requests to AoC
+	#[arg(long, global = true, value_name = "SECONDS")]
+	timeout: Option<u64>,
+	#[command(subcommand)]
+	command: Option<Command>,
+}
+
+#[derive(Subcommand)]
+enum Command {
+	/// Solve puzzles and submit their answers (the default)
+	Run {
+		#[arg(long)]
+		day: Option<u8>,
+		#[arg(long)]
+		part: Option<u8>,
+		/// Solve, but don't submit
+		#[arg(long)]
+		dry_run: bool,
+		#[arg(long, value_enum)]
+		format: Option<Format>,
+	},
+	/// Wait for a puzzle to unlock, fetch its input, and solve it in dry-run
+	Wait {
+		#[arg(long)]
+		day: u8,
+	},
+	/// Browse the whole calendar
+	Dashboard,
+	/// Stars and completion times of a private leaderboard, with ours
+	Leaderboard {
+		/// The leaderboard's JSON, as saved from AoC
+		#[arg(long, required_unless_present = "url", conflicts_with = "url")]
+		file: Option<PathBuf>,
+		/// Where to fetch the leaderboard's JSON from, with the session cookie if there is one
+		#[arg(long)]
+		url: Option<String>,
+		#[arg(long, value_enum)]
+		format: Option<Format>,
+	},
+	/// Serve the solvers over HTTP, on localhost
+	Serve {
+		/// 8023 unless configured otherwise
+		#[arg(long)]
+		port: Option<u16>,
+	},
+	/// Regenerate the progress table in the README
+	Report {
+		/// README.md unless configured otherwise
+		#[arg(long)]
+		readme: Option<PathBuf>,
+	},
+	/// Check every solver against its accepted answer, offline
+	Verify {
+		#[arg(long, value_enum)]
+		format: Option<Format>,
+	},
+	/// Encrypt the cached inputs and the ledger, to commit them
+	Seal,
+	/// Print a new key to encrypt inputs with
+	Keygen,
+}
+
+impl Cli {
+
+	/// The settings given as options, the top layer of the configuration
+	fn settings(&self) -> Settings {
+		let format = match &self.command {
+			Some(Command::Run { format, .. } | Command::Leaderboard { format, .. } | Command::Verify { format }) => *format,
+			_ => None,
+		};
+		let (port, readme) = match &self.command {
+			Some(Command::Serve { port }) => (*port, None),
+			Some(Command::Report { readme }) => (None, readme.clone()),
+			_ => (None, None),
+		};
+		Settings {
+			year: self.year,
+			inputs: self.inputs.clone(),
+			cache: self.cache.clone(),
+			submit: self.submit,
+			timeout: self.timeout,
+			format,
+			port,
+			readme,
+		}
+	}
+}
+
+fn main() -> ExitCode {
+	// report errors by their message, not their debug representation
+	if let Err(e) = aoc(Cli::parse()) {
+		eprintln!("Error: {e}");
+		return ExitCode::FAILURE;
+	}
+	ExitCode::SUCCESS
+}
+
+fn aoc(cli:Cli) -> Result<(),Box<dyn Error>> {
+	let _trace = cli.trace.as_deref().map(|file| trace::init(Some(file).filter(|f| *f != Path::new("-"))));
+	let settings = cli.settings();
+	config::init(Config::load(&cli.config, cli.profile.as_deref(), settings)?);
+	let command = cli.command.unwrap_or(Command::Run { day: None, part: None, dry_run: false, format: None });
+	let config = config::get();
+
+	match command {
+		Command::Run { day, part, dry_run, .. } => {
+			let cookie : String = env::var("COOKIE")?;
+			let submit = if dry_run { Submit::Never } else { config.submit };
+			run(&cookie, day, part, submit)?;
+		}
+		Command::Wait { day } => {
+			let cookie : String = env::var("COOKIE")?;
+			schedule::fetch_when_unlocked(
+				&SystemClock,
+				day,
+				|left| {
+					let secs = left.as_secs();
+					print!("\rDay {day} unlocks in {:02}:{:02}:{:02} ", secs / 3600, secs / 60 % 60, secs % 60);
+					let _ = io::stdout().flush();
+				},
+				|| input::load(&cookie, day),
+			)?;
+			println!("\rDay {day} input fetched        ");
+			if days::solver(day, 1).is_none() {
+				println!("There's no solver for day {day} yet");
+			}
+			run(&cookie, Some(day), None, Submit::Never)?;
+		}
+		Command::Dashboard => {
+			dashboard::show(env::var("COOKIE").unwrap_or_default())?;
+		}
+		Command::Leaderboard { file, url, .. } => {
+			let leaderboard = match (file, url) {
+				(Some(file), _) => Leaderboard::load(&file)?,
+				(_, Some(url)) => Leaderboard::fetch(&url, env::var("COOKIE").ok().as_deref())?,
+				_ => unreachable!(),
+			};
+			let ledger = Ledger::load(&Ledger::path())?;
+			print!("{}", leaderboard::analyze(&leaderboard, &ledger).render(config.format));
+		}
+		Command::Serve { .. } => {
+			let server = serve::bind(config.port)?;
+			println!("Serving the solvers on http://127.0.0.1:{}/{}", config.port, days::YEAR);
+			serve::run(server)?;
+		}
+		Command::Report { .. } => {
+			let readme = &config.readme;
+			let ledger = Ledger::load(&Ledger::path())?;
+			let timings = Timings::load(&Timings::path())?;
+			let criterion = report::criterion_dir();
+			let table = report::table(&ledger, &timings, |group, bench| report::median(&criterion, group, bench));
+			let text = report::splice(&fs::read_to_string(readme)?, &table)?;
+			fs::write(readme, text)?;
+		}
+		Command::Verify { .. } => {
+			let ledger = Ledger::load(&Ledger::path())?;
+			let mut table = Table::new(&["day", "part", "status", "time"]);
+			let mut failed = 0;
+			for solver in solvers()? {
+				let (check, run) = runner::verify(solver, &ledger)?;
+				failed += usize::from(check == Check::Failed);
+				table.push(vec![solver.day.into(), solver.part.into(), check.to_string().into(), run.map(|r| r.duration).into()]);
+			}
+			print!("{}", table.render(config.format));
+			if failed > 0 {
+				return Err(format!("{failed} solver(s) failed").into());
+			}
+		}
+		Command::Seal => {
+			let key = Key::load()?.ok_or(format!("no key, set {} or write one to {}", vault::KEY_VAR, vault::KEY_FILE))?;
+			let paths = (1..=25).map(input::path).chain([Ledger::path()]);
+			for path in paths.filter(|path| path.exists()) {
+				if vault::seal(&path, &key)? {
+					println!("Sealed {}", path.display());
+				}
+			}
+		}
+		Command::Keygen => {
+			println!("{}", Key::generate().to_hex());
+		}
+	}
 	Ok(())
 }
+
+/// The solvers, as long as they are for the configured year
+fn solvers() -> Result<&'static [Solver],Box<dyn Error>> {
+	match config::get().year {
+		days::YEAR => Ok(SOLVERS),
+		year => Err(format!("there are no solvers for {year}, only for {}", days::YEAR).into()),
+	}
+}
+
+fn run(cookie:&str, day:Option<u8>, part:Option<u8>, submit:Submit) -> Result<(),Box<dyn Error>> {
+	let mut runs = vec![];
+	let result = solvers()?
+		.iter()
+		.filter(|s| day.is_none_or(|d| d == s.day) && part.is_none_or(|p| p == s.part))
+		.try_for_each(|solver| {
+			let run = runner::run(cookie, solver, submit == Submit::Always)?;
+			if submit == Submit::Ask && confirm(&run)? {
+				runner::submit_answer(cookie, &run)?;
+			}
+			runs.push(run);
+			Ok(())
+		});
+	// what was solved is worth showing, even if something failed later
+	print!("{}", runner::table(&runs).render(config::get().format));
+	result
+}
+
+/// Ask whether to submit an answer, on stderr so that stdout stays machine readable
+fn confirm(run:&Run) -> io::Result<bool> {
+	eprint!("Submit {} for day {} part {}? [y/N] ", run.answer, run.day, run.part);
+	let mut reply = String::new();
+	io::stdin().read_line(&mut reply)?;
+	Ok(matches!(reply.trim(), "y" | "Y" | "yes"))
+}
+
+#[cfg(test)]
+mod tests {
+	use pretty_assertions::assert_eq;
+	use super::*;
+
+	#[test]
+	fn test_trace() {
+		// a bare --trace takes no value, so the next word is still the subcommand
+		let cli = Cli::try_parse_from(["aoc", "--trace", "keygen"]).unwrap();
+		assert_eq!(cli.trace, Some(PathBuf::from("-")));
+		assert!(matches!(cli.command, Some(Command::Keygen)));
+
+		let cli = Cli::try_parse_from(["aoc", "verify", "--trace=trace.json"]).unwrap();
+		assert_eq!(cli.trace, Some(PathBuf::from("trace.json")));
+		assert!(matches!(cli.command, Some(Command::Verify { .. })));
+
+		let cli = Cli::try_parse_from(["aoc", "keygen"]).unwrap();
+		assert_eq!(cli.trace, None);
+	}
+
+	#[test]
+	fn test_settings() {
+		let cli = Cli::try_parse_from([
+			"aoc", "--inputs", "puzzles", "run", "--cache=records", "--submit", "ask", "--timeout", "5", "--format", "json",
+		]).unwrap();
+		assert_eq!(cli.settings(), Settings {
+			inputs: Some("puzzles".into()),
+			cache: Some("records".into()),
+			submit: Some(Submit::Ask),
+			timeout: Some(5),
+			format: Some(Format::Json),
+			..Settings::default()
+		});
+		let cli = Cli::try_parse_from(["aoc", "--year", "2022", "serve", "--port", "8080"]).unwrap();
+		assert_eq!(cli.settings(), Settings { year: Some(2022), port: Some(8080), ..Settings::default() });
+	}
+}
@@ -0,0 +1,174 @@
+// Tabular results, as aligned text, JSON or CSV
+
+use std::{fmt::Write, time::Duration};
+use serde::Deserialize;
+use serde_json::{json, Map, Value};
+
+#[derive(clap::ValueEnum,Deserialize,Debug,Clone,Copy,Default,PartialEq,Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum Format {
+	#[default]
+	Text,
+	Json,
+	Csv,
+}
+
+#[derive(Debug,Clone,PartialEq)]
+pub enum Cell {
+	Text(String),
+	Int(i64),
+	Duration(Duration),
+	Empty,
+}
+
+impl Cell {
+	fn to_json(&self) -> Value {
+		match self {
+			Cell::Text(s) => json!(s),
+			Cell::Int(n) => json!(n),
+			// in seconds, as a fraction
+			Cell::Duration(d) => json!(d.as_secs_f64()),
+			Cell::Empty => Value::Null,
+		}
+	}
+	fn to_text(&self) -> String {
+		match self {
+			Cell::Text(s) => s.clone(),
+			Cell::Int(n) => n.to_string(),
+			Cell::Duration(d) if d.as_secs() >= 60 => {
+				let s = d.as_secs();
+				format!("{}:{:02}:{:02}", s / 3600, s / 60 % 60, s % 60)
+			}
+			Cell::Duration(d) => format!("{d:.1?}"),
+			Cell::Empty => String::new(),
+		}
+	}
+	fn to_csv(&self) -> String {
+		match self {
+			Cell::Text(s) if s.contains([',','"','\n']) => format!("\"{}\"", s.replace('"', "\"\"")),
+			Cell::Duration(d) => d.as_secs_f64().to_string(),
+			cell => cell.to_text(),
+		}
+	}
+}
+
+impl From<&str> for Cell {
+	fn from(s:&str) -> Self { Cell::Text(s.into()) }
+}
+
+impl From<String> for Cell {
+	fn from(s:String) -> Self { Cell::Text(s) }
+}
+
+impl From<Duration> for Cell {
+	fn from(d:Duration) -> Self { Cell::Duration(d) }
+}
+
+impl<T:Into<Cell>> From<Option<T>> for Cell {
+	fn from(o:Option<T>) -> Self { o.map_or(Cell::Empty, Into::into) }
+}
+
+macro_rules! int_cell {
+	($($t:ty),+) => { $(
+		impl From<$t> for Cell {
+			fn from(n:$t) -> Self { Cell::Int(n.into()) }
+		}
+	)+ };
+}
+int_cell!(u8, u32, i64);
+
+#[derive(Debug,Clone,PartialEq)]
+pub struct Table {
+	headers: Vec<&'static str>,
+	rows: Vec<Vec<Cell>>,
+}
+
+impl Table {
+
+	pub fn new(headers:&[&'static str]) -> Self {
+		Table { headers: headers.to_vec(), rows: vec![] }
+	}
+
+	pub fn push(&mut self, row:Vec<Cell>) {
+		assert_eq!(row.len(), self.headers.len());
+		self.rows.push(row);
+	}
+
+	pub fn render(&self, format:Format) -> String {
+		match format {
+			Format::Text => self.to_text(),
+			Format::Json => self.to_json(),
+			Format::Csv => self.to_csv(),
+		}
+	}
+
+	/// An array of objects, keyed by header
+	fn to_json(&self) -> String {
+		let rows:Vec<Value> = self.rows.iter().map(|row| {
+			let object:Map<String,Value> = self.headers.iter().zip(row).map(|(h,c)| (h.to_string(), c.to_json())).collect();
+			Value::Object(object)
+		})
+		.collect();
+		serde_json::to_string_pretty(&rows).unwrap() + "\n"
+	}
+
+	fn to_csv(&self) -> String {
+		let mut csv = self.headers.join(",") + "\n";
+		self.rows.iter().for_each(|row| {
+			let cells:Vec<String> = row.iter().map(Cell::to_csv).collect();
+			csv += &(cells.join(",") + "\n");
+		});
+		csv
+	}
+
+	fn to_text(&self) -> String {
+		let cells:Vec<Vec<String>> = self.rows.iter().map(|row| row.iter().map(Cell::to_text).collect()).collect();
+		let widths:Vec<usize> = self.headers.iter().enumerate().map(|(i,h)| {
+			cells.iter().map(|row| row[i].chars().count()).chain([h.len()]).max().unwrap()
+		})
+		.collect();
+
+		let mut text = String::new();
+		let mut line = |row:&[String]| {
+			let padded:Vec<String> = row.iter().zip(&widths).map(|(c,w)| format!("{c:w$}")).collect();
+			writeln!(text, "{}", padded.join("  ").trim_end()).unwrap();
+		};
+		line(&self.headers.iter().map(|h| h.to_string()).collect::<Vec<_>>());
+		cells.iter().for_each(|row| line(row));
+		text
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use pretty_assertions::assert_str_eq;
+	use super::*;
+
+	fn table() -> Table {
+		let mut table = Table::new(&["day", "answer", "time"]);
+		table.push(vec![1u8.into(), "142".into(), Duration::from_millis(2).into()]);
+		table.push(vec![12u8.into(), "a,\"b\"".into(), None::<Duration>.into()]);
+		table
+	}
+
+	#[test]
+	fn test_render() {
+		assert_str_eq!(table().render(Format::Text), "day  answer  time\n1    142     2.0ms\n12   a,\"b\"\n");
+		assert_str_eq!(table().render(Format::Csv), "day,answer,time\n1,142,0.002\n12,\"a,\"\"b\"\"\",\n");
+		assert_str_eq!(
+			table().render(Format::Json),
+			r#"[
+  {
+    "day": 1,
+    "answer": "142",
+    "time": 0.002
+  },
+  {
+    "day": 12,
+    "answer": "a,\"b\"",
+    "time": null
+  }
+]
+"#);
+	}
+}
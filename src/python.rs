@@ -0,0 +1,210 @@
+// Python bindings: the solvers, and the models days parse their inputs into
+
+use std::{collections::HashMap, panic::{self, AssertUnwindSafe}};
+use pyo3::{basic::CompareOp, exceptions::PyValueError, prelude::*, types::PyModule};
+
+use crate::{days::{self, day02, day05, day07, day08, SOLVERS}, runner, utils::parse};
+
+// parsers panic on bad input, which should be a ValueError in Python
+fn catch<T>(f:impl FnOnce() -> T) -> PyResult<T> {
+	panic::catch_unwind(AssertUnwindSafe(f)).map_err(|e| PyValueError::new_err(runner::panic_message(e)))
+}
+
+fn invalid(e:parse::Error) -> PyErr {
+	PyValueError::new_err(e.to_string())
+}
+
+/// A registered solver, called with a puzzle input for its answer
+#[pyclass(module = "aoc", frozen)]
+struct Solver {
+	#[pyo3(get)]
+	day: u8,
+	#[pyo3(get)]
+	part: u8,
+}
+
+#[pymethods]
+impl Solver {
+	fn __call__(&self, input:&str) -> PyResult<String> {
+		solve(self.day, self.part, input)
+	}
+	fn __repr__(&self) -> String {
+		format!("Solver(day={}, part={})", self.day, self.part)
+	}
+}
+
+/// Solve a puzzle part's input
+#[pyfunction]
+fn solve(day:u8, part:u8, input:&str) -> PyResult<String> {
+	let solver = days::solver(day, part).ok_or_else(|| PyValueError::new_err(format!("there's no solver for day {day} part {part}")))?;
+	runner::solve(solver, input).map(|run| run.answer).map_err(|e| PyValueError::new_err(e.to_string()))
+}
+
+#[pyclass(module = "aoc.day02", frozen)]
+struct Game(day02::game::Game);
+
+#[pymethods]
+impl Game {
+	#[new]
+	fn new(line:&str) -> PyResult<Self> {
+		parse::all(line, day02::game::game).map(Game).map_err(invalid)
+	}
+	#[getter]
+	fn id(&self) -> u32 {
+		self.0.id
+	}
+	/// (red, green, blue) cubes of every hand
+	#[getter]
+	fn hands(&self) -> Vec<(u32,u32,u32)> {
+		self.0.hands.iter().map(|h| (h.red, h.green, h.blue)).collect()
+	}
+	fn __repr__(&self) -> String {
+		format!("Game(id={}, hands={:?})", self.0.id, self.hands())
+	}
+}
+
+#[pyfunction(name = "parse")]
+fn parse_games(input:&str) -> PyResult<Vec<Game>> {
+	Ok(day02::try_parse(input).map_err(invalid)?.into_iter().map(Game).collect())
+}
+
+#[pyclass(module = "aoc.day05", frozen)]
+struct Map(day05::map::Map);
+
+#[pymethods]
+impl Map {
+	/// From a "<src>-to-<dst> map:" block
+	#[new]
+	fn new(block:&str) -> PyResult<Self> {
+		day05::map::Map::try_from(block).map(Map).map_err(invalid)
+	}
+	#[getter]
+	fn src(&self) -> String {
+		format!("{:?}", self.0.src).to_lowercase()
+	}
+	#[getter]
+	fn dst(&self) -> String {
+		format!("{:?}", self.0.dst).to_lowercase()
+	}
+	fn map(&self, v:u64) -> u64 {
+		self.0.map(v)
+	}
+	fn __repr__(&self) -> String {
+		format!("Map({} -> {})", self.src(), self.dst())
+	}
+}
+
+/// The seeds, and the maps to take them through
+#[pyfunction(name = "parse")]
+fn parse_almanac(input:&str) -> PyResult<(Vec<u64>,Vec<Map>)> {
+	let (seeds, maps) = day05::try_parse(input).map_err(invalid)?;
+	Ok((seeds, maps.into_iter().map(Map).collect()))
+}
+
+/// Hands compare by their strength
+#[pyclass(module = "aoc.day07", frozen)]
+struct Hand(day07::hand::Hand);
+
+#[pymethods]
+impl Hand {
+	#[new]
+	fn new(line:&str) -> PyResult<Self> {
+		catch(|| Hand(day07::hand::Hand::new(line)))
+	}
+	#[getter]
+	fn bid(&self) -> u32 {
+		self.0.bid
+	}
+	#[getter]
+	fn cards(&self) -> String {
+		self.0.cards.iter().map(|c| c.value).collect()
+	}
+	#[getter]
+	fn category(&self) -> String {
+		format!("{:?}", self.0.category)
+	}
+	fn __richcmp__(&self, other:&Self, op:CompareOp) -> bool {
+		// the stronger hand sorts first in Rust, but should be the greater one
+		op.matches(other.0.cmp(&self.0))
+	}
+	fn __repr__(&self) -> String {
+		format!("Hand({} {}, {})", self.cards(), self.0.bid, self.category())
+	}
+}
+
+#[pyfunction(name = "parse")]
+fn parse_hands(input:&str) -> PyResult<Vec<Hand>> {
+	catch(|| day07::parse(input).into_iter().map(Hand).collect())
+}
+
+#[pyclass(module = "aoc.day08", frozen)]
+struct Network(day08::Network);
+
+#[pymethods]
+impl Network {
+	#[new]
+	fn new(input:&str) -> PyResult<Self> {
+		day08::try_parse(input).map(Network).map_err(invalid)
+	}
+	#[getter]
+	fn directions(&self) -> String {
+		self.0.0.iter().collect()
+	}
+	/// node -> (left, right)
+	#[getter]
+	fn nodes(&self) -> PyResult<HashMap<String,(String,String)>> {
+		let graph = &self.0.1;
+		graph.nodes()
+			.map(|(id,n)| match graph.successors(id) {
+				&[l,r] => Ok((n.to_string(), (graph.label(l).to_string(), graph.label(r).to_string()))),
+				next => Err(PyValueError::new_err(format!("node {n} leads to {} nodes, not a left and a right one", next.len()))),
+			})
+			.collect()
+	}
+	/// Steps from AAA to ZZZ
+	fn steps(&self) -> PyResult<String> {
+		catch(|| day08::part1(&self.0))
+	}
+	fn __repr__(&self) -> String {
+		format!("Network({} directions, {} nodes)", self.0.0.len(), self.0.1.len())
+	}
+}
+
+#[pymodule]
+fn aoc(m:&Bound<'_,PyModule>) -> PyResult<()> {
+
+	let py = m.py();
+	m.add_function(wrap_pyfunction!(solve, m)?)?;
+	m.add_class::<Solver>()?;
+	m.add("solvers", SOLVERS.iter().map(|s| Solver { day: s.day, part: s.part }).collect::<Vec<_>>())?;
+
+	// a submodule per day, with its solver and parsed types
+	let days = (1..=25).filter(|&day| days::solver(day, 1).is_some());
+	for day in days {
+		let name = format!("day{day:02}");
+		let sub = PyModule::new(py, &name)?;
+		sub.add("solve", Solver { day, part: 1 })?;
+		match day {
+			2 => {
+				sub.add_class::<Game>()?;
+				sub.add_function(wrap_pyfunction!(parse_games, &sub)?)?;
+			}
+			5 => {
+				sub.add_class::<Map>()?;
+				sub.add_function(wrap_pyfunction!(parse_almanac, &sub)?)?;
+			}
+			7 => {
+				sub.add_class::<Hand>()?;
+				sub.add_function(wrap_pyfunction!(parse_hands, &sub)?)?;
+			}
+			8 => {
+				sub.add_class::<Network>()?;
+			}
+			_ => {}
+		}
+		m.add_submodule(&sub)?;
+		// so "from aoc.day07 import Hand" works too
+		py.import("sys")?.getattr("modules")?.set_item(format!("aoc.{name}"), &sub)?;
+	}
+	Ok(())
+}
@@ -0,0 +1,149 @@
+// The progress table in the README
+
+use std::{env, ffi::OsString, fmt::Write, fs, io, path::{Path, PathBuf}, time::Duration};
+use serde::Deserialize;
+
+use crate::{
+	days::{self, YEAR},
+	ledger::{Ledger, Verdict},
+	timings::Timings,
+};
+
+pub const START:&str = "<!-- progress -->";
+pub const END:&str = "<!-- /progress -->";
+
+#[derive(Deserialize)]
+struct Estimates {
+	median: Estimate,
+}
+
+#[derive(Deserialize)]
+struct Estimate {
+	/// in nanoseconds
+	point_estimate: f64,
+}
+
+/// Median time of a benchmark, as last recorded by criterion
+pub fn median(criterion_dir:&Path, group:&str, bench:&str) -> Option<Duration> {
+	let path = criterion_dir.join(group).join(bench).join("new").join("estimates.json");
+	let estimates:Estimates = serde_json::from_str(&fs::read_to_string(path).ok()?).ok()?;
+	Some(Duration::from_nanos(estimates.median.point_estimate as u64))
+}
+
+/// Where criterion keeps its results, found the way criterion finds it
+pub fn criterion_dir() -> PathBuf {
+	criterion_dir_from(|name| env::var_os(name))
+}
+
+// CRITERION_HOME, or else criterion/ in the target directory
+fn criterion_dir_from(var:impl Fn(&str) -> Option<OsString>) -> PathBuf {
+	match (var("CRITERION_HOME"), var("CARGO_TARGET_DIR")) {
+		(Some(home), _) => home.into(),
+		(None, Some(target)) => Path::new(&target).join("criterion"),
+		(None, None) => PathBuf::from_iter(["target", "criterion"]),
+	}
+}
+
+// how a part stands, without giving away any answer
+fn status(ledger:&Ledger, timings:&Timings, day:u8, part:u8) -> String {
+	let submitted = |verdict:&dyn Fn(Verdict) -> bool| ledger.submissions.iter().filter(|s| s.day == day && s.part == part && verdict(s.verdict)).count();
+	let rejected = submitted(&|v| !matches!(v, Verdict::Correct | Verdict::Unverified));
+	if ledger.accepted(day, part).is_some() {
+		"accepted".into()
+	} else if submitted(&|v| v == Verdict::Unverified) > 0 {
+		"unverified".into()
+	} else if rejected > 0 {
+		format!("rejected ×{rejected}")
+	} else if timings.get(day, part).is_some() {
+		"not submitted".into()
+	} else if days::solver(day, part).is_some() {
+		"not run".into()
+	} else {
+		"—".into()
+	}
+}
+
+/// A markdown table of every day with a solver.
+/// The runtime of a part is that of parsing plus solving it, as the median reported by `median`.
+pub fn table(ledger:&Ledger, timings:&Timings, median:impl Fn(&str,&str) -> Option<Duration>) -> String {
+
+	let mut table = String::from("| Day | Puzzle | Stars | Part 1 | Part 2 | Median runtime |\n|---:|---|---|---|---|---|\n");
+
+	let days = (1..=25).filter(|&day| days::solver(day, 1).is_some());
+	for day in days {
+		let title = days::title(day).unwrap_or("?");
+		let group = format!("day{day:02}");
+		let runtimes:Vec<String> = [1,2]
+			.into_iter()
+			.filter(|&part| days::solver(day, part).is_some())
+			.map(|part| {
+				median(&group, "parse")
+					.zip(median(&group, &format!("part{part}")))
+					.map_or("—".into(), |(parse,solve)| format!("{:.1?}", parse + solve))
+			})
+			.collect();
+		writeln!(
+			table,
+			"| {day} | [{title}](https://adventofcode.com/{YEAR}/day/{day}) | {} | {} | {} | {} |",
+			"⭐".repeat(ledger.stars(day)),
+			status(ledger, timings, day, 1),
+			status(ledger, timings, day, 2),
+			runtimes.join(" / "),
+		).unwrap();
+	}
+	table
+}
+
+/// Replace what is between the progress markers with the table
+pub fn splice(readme:&str, table:&str) -> io::Result<String> {
+	let missing = || io::Error::new(io::ErrorKind::InvalidData, format!("the README has no \"{START}\" ... \"{END}\" markers"));
+	let (before, rest) = readme.split_once(START).ok_or_else(missing)?;
+	let (_, after) = rest.split_once(END).ok_or_else(missing)?;
+	Ok(format!("{before}{START}\n{table}{END}{after}"))
+}
+
+#[cfg(test)]
+mod tests {
+	use pretty_assertions::assert_str_eq;
+	use crate::{ledger::Submission, timings::Timing};
+	use super::*;
+
+	#[test]
+	fn test_table() {
+		let mut ledger = Ledger::default();
+		ledger.record(Submission { day: 1, part: 1, answer: "142".into(), verdict: Verdict::Correct, at: 0 });
+		ledger.record(Submission { day: 2, part: 1, answer: "9".into(), verdict: Verdict::TooHigh, at: 0 });
+		ledger.record(Submission { day: 5, part: 1, answer: "35".into(), verdict: Verdict::Unverified, at: 0 });
+		let mut timings = Timings::default();
+		timings.record(Timing { day: 3, part: 1, answer: "4361".into(), duration: Duration::ZERO, at: 0 });
+
+		let median = |group:&str, bench:&str| match (group,bench) {
+			("day01","parse") => Some(Duration::from_micros(10)),
+			("day01","part1") => Some(Duration::from_micros(5)),
+			_ => None,
+		};
+
+		let table = table(&ledger, &timings, median);
+		let mut lines = table.lines().skip(2);
+		assert_str_eq!(lines.next().unwrap(), "| 1 | [Trebuchet?!](https://adventofcode.com/2023/day/1) | ⭐ | accepted | — | 15.0µs |");
+		assert_str_eq!(lines.next().unwrap(), "| 2 | [Cube Conundrum](https://adventofcode.com/2023/day/2) |  | rejected ×1 | — | — |");
+		assert_str_eq!(lines.next().unwrap(), "| 3 | [Gear Ratios](https://adventofcode.com/2023/day/3) |  | not submitted | — | — |");
+		assert_str_eq!(lines.next().unwrap(), "| 4 | [Scratchcards](https://adventofcode.com/2023/day/4) |  | not run | — | — |");
+		assert_str_eq!(lines.next().unwrap(), "| 5 | [If You Give A Seed A Fertilizer](https://adventofcode.com/2023/day/5) |  | unverified | — | — |");
+	}
+
+	#[test]
+	fn test_criterion_dir() {
+		let vars = |vars:&'static [(&str, &str)]| move |name:&str| vars.iter().find(|(n, _)| *n == name).map(|(_, v)| OsString::from(v));
+		assert_eq!(criterion_dir_from(vars(&[])), Path::new("target/criterion"));
+		assert_eq!(criterion_dir_from(vars(&[("CARGO_TARGET_DIR", "/tmp/build")])), Path::new("/tmp/build/criterion"));
+		assert_eq!(criterion_dir_from(vars(&[("CARGO_TARGET_DIR", "/tmp/build"), ("CRITERION_HOME", "benches")])), Path::new("benches"));
+	}
+
+	#[test]
+	fn test_splice() {
+		let readme = format!("# Title\n\n{START}\nold\n{END}\n\nMore\n");
+		assert_str_eq!(splice(&readme, "new\n").unwrap(), format!("# Title\n\n{START}\nnew\n{END}\n\nMore\n"));
+		assert!(splice("# Title\n", "new\n").is_err());
+	}
+}
@@ -0,0 +1,167 @@
+// Fetch a puzzle's input, solve it, and submit the answer
+
+use std::{any::Any, fmt, io, panic, time::{Duration, Instant, SystemTime, UNIX_EPOCH}};
+
+use crate::{
+	days::Solver,
+	error::{Error, Result},
+	input,
+	output::Table,
+	ledger::{Ledger, Submission, Verdict},
+	submit::{self, Response},
+	timings::{Timing, Timings},
+};
+use tracing::{debug, info, info_span};
+
+#[derive(Debug,Clone,PartialEq,Eq)]
+pub struct Run {
+	pub day: u8,
+	pub part: u8,
+	pub answer: String,
+	pub duration: Duration,
+}
+
+/// Runs as a table, to print or export
+pub fn table(runs:&[Run]) -> Table {
+	let mut table = Table::new(&["day", "part", "answer", "time"]);
+	runs.iter().for_each(|run| table.push(vec![run.day.into(), run.part.into(), run.answer.as_str().into(), run.duration.into()]));
+	table
+}
+
+/// Seconds since the unix epoch
+pub fn now() -> u64 {
+	SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs()
+}
+
+/// Time a solver, turning its panics into errors
+pub fn solve(solver:&Solver, input:&str) -> Result<Run> {
+	let start = Instant::now();
+	let answer = panic::catch_unwind(|| (solver.solve)(input)).map_err(|e| Error::Panic(panic_message(e)))?;
+	let duration = start.elapsed();
+	info!(answer, ?duration, "solved");
+	Ok(Run { day: solver.day, part: solver.part, answer, duration })
+}
+
+/// The message a panic was raised with
+pub fn panic_message(payload:Box<dyn Any + Send>) -> String {
+	payload.downcast_ref::<&str>().map(|s| s.to_string())
+		.or_else(|| payload.downcast_ref::<String>().cloned())
+		.unwrap_or_default()
+}
+
+/// Solve a puzzle part, record how long it took, and optionally submit the answer
+pub fn run(session:&str, solver:&Solver, submit:bool) -> Result<Run> {
+	let _span = info_span!("run", day = solver.day, part = solver.part).entered();
+
+	let input = info_span!("fetch", day = solver.day).in_scope(|| input::load(session, solver.day))?;
+	debug!(bytes = input.len(), lines = input.lines().count(), "input");
+	let run = solve(solver, &input)?;
+
+	let path = Timings::path();
+	let mut timings = Timings::load(&path)?;
+	timings.record(Timing {
+		day: run.day,
+		part: run.part,
+		answer: run.answer.clone(),
+		duration: run.duration,
+		at: now(),
+	});
+	timings.save(&path)?;
+
+	if submit {
+		info_span!("submit").in_scope(|| submit_answer(session, &run))?;
+	}
+
+	Ok(run)
+}
+
+/// How a solver fared against the answer accepted for its part
+#[derive(Debug,Clone,Copy,PartialEq,Eq)]
+pub enum Check {
+	Passed,
+	Failed,
+	NoInput,
+	NoAnswer,
+}
+
+impl fmt::Display for Check {
+	fn fmt(&self, f:&mut fmt::Formatter) -> fmt::Result {
+		f.write_str(match self {
+			Check::Passed => "passed",
+			Check::Failed => "failed",
+			Check::NoInput => "no input",
+			Check::NoAnswer => "no answer",
+		})
+	}
+}
+
+/// Solve a part offline, from its cached input, and compare with the accepted answer
+pub fn verify(solver:&Solver, ledger:&Ledger) -> Result<(Check, Option<Run>)> {
+	let _span = info_span!("verify", day = solver.day, part = solver.part).entered();
+	let Some(accepted) = ledger.accepted(solver.day, solver.part) else {
+		return Ok((Check::NoAnswer, None));
+	};
+	let input = match input::cached(solver.day) {
+		Ok(input) => input,
+		Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok((Check::NoInput, None)),
+		Err(e) => return Err(e.into()),
+	};
+	match solve(solver, &input) {
+		Ok(run) if run.answer == accepted => Ok((Check::Passed, Some(run))),
+		Ok(run) => Ok((Check::Failed, Some(run))),
+		Err(_) => Ok((Check::Failed, None)),
+	}
+}
+
+/// Post an answer, unless it is known to be wrong, keeping the verdict in the ledger
+pub fn submit_answer(session:&str, run:&Run) -> Result<()> {
+
+	let path = Ledger::path();
+	let mut ledger = Ledger::load(&path)?;
+
+	ledger.check(run.day, run.part, &run.answer)?;
+	if ledger.accepted(run.day, run.part).is_some() || ledger.unverified(run.day, run.part, &run.answer) {
+		// no need to submit it again, AoC wouldn't tell more
+		return Ok(());
+	}
+
+	let verdict = match submit::post(session, run.day, run.part, &run.answer)? {
+		Response::Verdict(verdict) => verdict,
+		Response::AlreadyComplete => Verdict::Unverified,
+		Response::RateLimited(wait) => return Err(Error::RateLimited(wait)),
+	};
+	ledger.record(Submission {
+		day: run.day,
+		part: run.part,
+		answer: run.answer.clone(),
+		verdict,
+		at: now(),
+	});
+	ledger.save(&path)?;
+	match verdict {
+		Verdict::Correct => Ok(()),
+		Verdict::Unverified => Err(Error::Unverified(run.answer.clone())),
+		wrong => Err(Error::Wrong(wrong)),
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use pretty_assertions::assert_eq;
+	use super::*;
+
+	#[test]
+	fn test_solve() {
+		let solver = Solver { day: 1, part: 1, solve: crate::days::day01::solve };
+		let run = solve(&solver, "a1b2c3\n").unwrap();
+		assert_eq!(run.answer, "13");
+		assert!(matches!(solve(&solver, "no digits\n"), Err(Error::Panic(_))));
+	}
+
+	#[test]
+	fn test_verify() {
+		// no accepted answer, so the input isn't even looked for
+		let solver = Solver { day: 25, part: 1, solve: crate::days::day01::solve };
+		assert_eq!(verify(&solver, &Ledger::default()).unwrap(), (Check::NoAnswer, None));
+	}
+}
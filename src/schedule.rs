@@ -0,0 +1,154 @@
+// Waiting for a puzzle to unlock, and fetching its input as soon as it does
+
+use std::{cmp::min, thread, time::{Duration, SystemTime, UNIX_EPOCH}};
+
+use crate::{config, error::{Error, Result}};
+
+/// Where the time comes from, so waiting can be tested without waiting
+pub trait Clock {
+	fn now(&self) -> SystemTime;
+	fn sleep(&self, duration:Duration);
+}
+
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+	fn now(&self) -> SystemTime { SystemTime::now() }
+	fn sleep(&self, duration:Duration) { thread::sleep(duration) }
+}
+
+const RETRIES:u32 = 5;
+const RETRY_DELAY:Duration = Duration::from_secs(5);
+
+// days since the unix epoch of a date in the proleptic gregorian calendar
+fn days_from_civil(year:i64, month:i64, day:i64) -> i64 {
+	let y = if month <= 2 { year - 1 } else { year };
+	let era = y.div_euclid(400);
+	let yoe = y - era * 400;
+	let doy = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+	let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+	era * 146097 + doe - 719468
+}
+
+/// Puzzles unlock at midnight US Eastern, which in December is UTC-5
+pub fn unlock_time(day:u8) -> SystemTime {
+	let days = days_from_civil(i64::from(config::get().year), 12, i64::from(day));
+	let secs = u64::try_from(days * 86400).unwrap() + 5 * 3600;
+	UNIX_EPOCH + Duration::from_secs(secs)
+}
+
+// up to a couple of seconds, so we aren't one of many requests at exactly the unlock time
+fn jitter(clock:&impl Clock) -> Duration {
+	let nanos = clock.now().duration_since(UNIX_EPOCH).unwrap().subsec_nanos();
+	Duration::from_millis(500 + u64::from(nanos % 2000))
+}
+
+// what may be gone by the next attempt: the puzzle not being live yet, or the network
+fn transient(e:&Error) -> bool {
+	match e {
+		Error::Http(e) => matches!(**e, ureq::Error::Status(404, _) | ureq::Error::Transport(_)),
+		_ => false,
+	}
+}
+
+/// Wait until a day unlocks, reporting the time left every second, then fetch its input,
+/// retrying a few times since the puzzle may not be live the very moment it should.
+/// Other errors, like a rejected download, are returned right away.
+pub fn fetch_when_unlocked(
+	clock:&impl Clock,
+	day:u8,
+	mut countdown:impl FnMut(Duration),
+	mut fetch:impl FnMut() -> Result<String>,
+) -> Result<String> {
+
+	let unlock = unlock_time(day);
+	while let Ok(left) = unlock.duration_since(clock.now()) {
+		if left.is_zero() {
+			break;
+		}
+		countdown(left);
+		clock.sleep(min(left, Duration::from_secs(1)));
+	}
+	clock.sleep(jitter(clock));
+
+	let mut attempt = 1;
+	loop {
+		match fetch() {
+			Err(e) if transient(&e) && attempt < RETRIES => {
+				clock.sleep(RETRY_DELAY * attempt);
+				attempt += 1;
+			}
+			result => return result,
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use std::cell::{Cell, RefCell};
+	use pretty_assertions::assert_eq;
+	use crate::input::Rejected;
+	use super::*;
+
+	struct FakeClock(Cell<SystemTime>);
+
+	impl Clock for FakeClock {
+		fn now(&self) -> SystemTime { self.0.get() }
+		fn sleep(&self, duration:Duration) { self.0.set(self.0.get() + duration) }
+	}
+
+	// how AoC answers before the puzzle is live
+	fn unavailable() -> Error {
+		ureq::Error::Status(404, ureq::Response::new(404, "Not Found", "").unwrap()).into()
+	}
+
+	#[test]
+	fn test_unlock_time() {
+		// 2023-12-01T05:00:00Z
+		assert_eq!(unlock_time(1), UNIX_EPOCH + Duration::from_secs(1701406800));
+		assert_eq!(unlock_time(25).duration_since(unlock_time(1)).unwrap(), Duration::from_secs(24 * 86400));
+	}
+
+	#[test]
+	fn test_fetch_when_unlocked() {
+		let clock = FakeClock(Cell::new(unlock_time(5) - Duration::from_secs(10)));
+		let countdowns = RefCell::new(vec![]);
+		let mut fetches = 0;
+
+		let input = fetch_when_unlocked(
+			&clock,
+			5,
+			|left| countdowns.borrow_mut().push(left.as_secs()),
+			|| {
+				fetches += 1;
+				// not live until the third attempt
+				assert!(clock.now() > unlock_time(5));
+				if fetches < 3 { Err(unavailable()) } else { Ok("seeds: 1\n".into()) }
+			},
+		);
+
+		assert_eq!(input.unwrap(), "seeds: 1\n");
+		assert_eq!(countdowns.into_inner(), (1..=10).rev().collect::<Vec<u64>>());
+		assert_eq!(fetches, 3);
+		let waited = clock.now().duration_since(unlock_time(5)).unwrap();
+		assert!(waited >= RETRY_DELAY * 3 && waited < RETRY_DELAY * 3 + Duration::from_secs(3));
+	}
+
+	#[test]
+	fn test_fetch_gives_up() {
+		let clock = FakeClock(Cell::new(unlock_time(5) + Duration::from_secs(60)));
+		let mut fetches = 0;
+		let input = fetch_when_unlocked(&clock, 5, |_| panic!("already unlocked"), || { fetches += 1; Err(unavailable()) });
+		assert!(input.is_err());
+		assert_eq!(fetches, RETRIES);
+	}
+
+	#[test]
+	fn test_fetch_rejected() {
+		let clock = FakeClock(Cell::new(unlock_time(5) + Duration::from_secs(60)));
+		let mut fetches = 0;
+		let input = fetch_when_unlocked(&clock, 5, |_| panic!("already unlocked"), || { fetches += 1; Err(Rejected::LoggedOut.into()) });
+		assert!(matches!(input, Err(Error::Input(Rejected::LoggedOut))));
+		assert_eq!(fetches, 1);
+	}
+}
@@ -0,0 +1,118 @@
+// Solvers as a local HTTP service:
+//   GET  /2023                the registered solvers
+//   POST /2023/{day}/{part}   solve the input in the body, for its answer and timing
+
+use std::io;
+use serde_json::{json, Value};
+use tiny_http::{Header, Request, Response, Server};
+use tracing::warn;
+
+use crate::{days::{self, SOLVERS, YEAR}, runner};
+
+/// Route a request, to a status code and a JSON body
+pub fn handle(method:&str, path:&str, input:&str) -> (u16, Value) {
+
+	let error = |status, msg:String| (status, json!({ "error": msg }));
+	let year = YEAR.to_string();
+	let segments:Vec<&str> = path.trim_matches('/').split('/').collect();
+	let (first, rest) = segments.split_first().unwrap();
+	if *first != year {
+		return error(404, format!("nothing at {path}"));
+	}
+
+	match (method, rest) {
+		("GET", []) => {
+			let solvers:Vec<Value> = SOLVERS.iter().map(|s| json!({
+				"year": YEAR,
+				"day": s.day,
+				"part": s.part,
+				"title": days::title(s.day),
+				"path": format!("/{YEAR}/{}/{}", s.day, s.part),
+			}))
+			.collect();
+			(200, Value::Array(solvers))
+		}
+		("POST", [day, part]) => {
+			let solver = day.parse().ok().zip(part.parse().ok()).and_then(|(d,p)| days::solver(d, p));
+			let Some(solver) = solver else {
+				return error(404, format!("there's no solver for {path}"));
+			};
+			match runner::solve(solver, input) {
+				Ok(run) => (200, json!({
+					"year": YEAR,
+					"day": run.day,
+					"part": run.part,
+					"answer": run.answer,
+					// in seconds, as a fraction
+					"time": run.duration.as_secs_f64(),
+				})),
+				Err(e) => error(422, e.to_string()),
+			}
+		}
+		(_, []) | (_, [_,_]) => error(405, format!("{method} {path} is not supported")),
+		_ => error(404, format!("nothing at {path}")),
+	}
+}
+
+/// Listen on a local port, 0 for any free one
+pub fn bind(port:u16) -> io::Result<Server> {
+	Server::http(("127.0.0.1", port)).map_err(io::Error::other)
+}
+
+/// Answer requests until the listener fails; a client going away mid-response doesn't stop it
+pub fn run(server:Server) -> io::Result<()> {
+	loop {
+		let request = server.recv()?;
+		let url = request.url().to_string();
+		if let Err(e) = respond(request) {
+			warn!(url, error = %e, "couldn't respond");
+		}
+	}
+}
+
+fn respond(mut request:Request) -> io::Result<()> {
+	let mut input = String::new();
+	let (status, body) = match request.as_reader().read_to_string(&mut input) {
+		Ok(_) => handle(request.method().as_str(), request.url(), &input),
+		Err(e) => (400, json!({ "error": e.to_string() })),
+	};
+	let header = Header::from_bytes("Content-Type", "application/json").unwrap();
+	request.respond(Response::from_string(body.to_string()).with_status_code(status).with_header(header))
+}
+
+#[cfg(test)]
+mod tests {
+	use std::thread;
+	use pretty_assertions::assert_eq;
+	use super::*;
+
+	#[test]
+	fn test_handle() {
+		let (status, list) = handle("GET", "/2023", "");
+		assert_eq!(status, 200);
+		assert_eq!(list[0], json!({ "year": 2023, "day": 1, "part": 1, "title": "Trebuchet?!", "path": "/2023/1/1" }));
+
+		let (status, run) = handle("POST", "/2023/1/1", "1abc2\npqr3stu8vwx\n");
+		assert_eq!(status, 200);
+		assert_eq!(run["answer"], "50");
+
+		assert_eq!(handle("POST", "/2023/1/1", "no digits\n").0, 422);
+		assert_eq!(handle("POST", "/2023/25/2", "").0, 404);
+		assert_eq!(handle("POST", "/2022/1/1", "").0, 404);
+		assert_eq!(handle("GET", "/2023/1/1", "").0, 405);
+		assert_eq!(handle("GET", "/", "").0, 404);
+	}
+
+	#[test]
+	fn test_serve() {
+		let server = bind(0).unwrap();
+		let port = server.server_addr().to_ip().unwrap().port();
+		thread::spawn(move || run(server));
+
+		let url = format!("http://127.0.0.1:{port}/2023/7/1");
+		let response = ureq::post(&url).send_string("32T3K 765\nT55J5 684\nKK677 28\nKTJJT 220\nQQQJA 483\n").unwrap();
+		assert_eq!(response.content_type(), "application/json");
+		let run:Value = serde_json::from_str(&response.into_string().unwrap()).unwrap();
+		assert_eq!(run["answer"], "6440");
+	}
+}
@@ -0,0 +1,24 @@
+// Local JSON files the runner keeps its records in
+
+use std::{fs, io, path::Path};
+use serde::{de::DeserializeOwned, Serialize};
+
+use crate::vault;
+
+/// Load a record file, decrypting it if only its encrypted version is there,
+/// or its default value when there is none yet
+pub fn load<T:DeserializeOwned+Default>(path:&Path) -> io::Result<T> {
+	match vault::read_to_string(path) {
+		Ok(json) => serde_json::from_str(&json).map_err(io::Error::other),
+		Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(T::default()),
+		Err(e) => Err(e),
+	}
+}
+
+pub fn save<T:Serialize>(path:&Path, value:&T) -> io::Result<()> {
+	if let Some(dir) = path.parent() {
+		fs::create_dir_all(dir)?;
+	}
+	let json = serde_json::to_string_pretty(value).map_err(io::Error::other)?;
+	fs::write(path, json)
+}
@@ -0,0 +1,64 @@
+// Posting answers to the AoC website
+
+use crate::{config, error::{Error, Result}, ledger::Verdict};
+
+#[derive(Debug,Clone,PartialEq,Eq)]
+pub enum Response {
+	Verdict(Verdict),
+	/// the part was completed already, so the answer wasn't checked
+	AlreadyComplete,
+	/// with how long is left to wait
+	RateLimited(String),
+}
+
+pub fn parse_response(body:&str) -> Option<Response> {
+	const WAIT:&str = "You have ";
+	if body.contains("That's the right answer") {
+		Some(Response::Verdict(Verdict::Correct))
+	} else if body.contains("your answer is too high") {
+		Some(Response::Verdict(Verdict::TooHigh))
+	} else if body.contains("your answer is too low") {
+		Some(Response::Verdict(Verdict::TooLow))
+	} else if body.contains("That's not the right answer") {
+		Some(Response::Verdict(Verdict::Incorrect))
+	} else if body.contains("You gave an answer too recently") {
+		let wait = body
+			.split_once(WAIT)
+			.and_then(|(_,rest)| rest.split_once(" left to wait"))
+			.map(|(wait,_)| wait.to_string())
+			.unwrap_or_default();
+		Some(Response::RateLimited(wait))
+	} else if body.contains("Did you already complete it?") {
+		Some(Response::AlreadyComplete)
+	} else {
+		None
+	}
+}
+
+pub fn post(session:&str, day:u8, part:u8, answer:&str) -> Result<Response> {
+	let config = config::get();
+	let url = format!("https://adventofcode.com/{}/day/{day}/answer", config.year);
+	let body = config.agent().post(&url)
+		.set("Cookie", &format!("session={session}"))
+		.send_form(&[("level", &part.to_string()), ("answer", answer)])?
+		.into_string()?;
+	parse_response(&body).ok_or(Error::UnexpectedResponse)
+}
+
+#[cfg(test)]
+mod tests {
+	use pretty_assertions::assert_eq;
+	use super::*;
+
+	#[test]
+	fn test_parse_response() {
+		let response = |p:&str| parse_response(&format!("<main>\n<article><p>{p}</p></article>\n</main>"));
+		assert_eq!(response("That's the right answer!  You are one gold star closer."), Some(Response::Verdict(Verdict::Correct)));
+		assert_eq!(response("That's not the right answer; your answer is too high.  If you're stuck, ..."), Some(Response::Verdict(Verdict::TooHigh)));
+		assert_eq!(response("That's not the right answer; your answer is too low."), Some(Response::Verdict(Verdict::TooLow)));
+		assert_eq!(response("That's not the right answer.  If you're stuck, ..."), Some(Response::Verdict(Verdict::Incorrect)));
+		assert_eq!(response("You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 37s left to wait."), Some(Response::RateLimited("37s".into())));
+		assert_eq!(response("You don't seem to be solving the right level.  Did you already complete it?"), Some(Response::AlreadyComplete));
+		assert_eq!(response("Something else"), None);
+	}
+}
@@ -0,0 +1,51 @@
+// The latest run of every puzzle part: its answer and how long it took
+
+use std::{io, path::{Path, PathBuf}, time::Duration};
+use serde::{Deserialize, Serialize};
+
+use crate::{config, store};
+
+#[derive(Serialize,Deserialize,Debug,Clone,PartialEq,Eq)]
+pub struct Timing {
+	pub day: u8,
+	pub part: u8,
+	pub answer: String,
+	pub duration: Duration,
+	/// seconds since the unix epoch
+	pub at: u64,
+}
+
+#[derive(Serialize,Deserialize,Debug,Default)]
+pub struct Timings {
+	pub runs: Vec<Timing>,
+}
+
+impl Timings {
+
+	pub fn path() -> PathBuf {
+		config::get().cache_dir().join("timings.json")
+	}
+
+	pub fn load(path:&Path) -> io::Result<Self> {
+		store::load(path)
+	}
+
+	pub fn save(&self, path:&Path) -> io::Result<()> {
+		store::save(path, self)
+	}
+
+	/// Keep a run, replacing the previous one for the same part
+	pub fn record(&mut self, timing:Timing) {
+		self.runs.retain(|t| (t.day,t.part) != (timing.day,timing.part));
+		self.runs.push(timing);
+	}
+
+	pub fn get(&self, day:u8, part:u8) -> Option<&Timing> {
+		self.runs.iter().find(|t| t.day == day && t.part == part)
+	}
+
+	/// The most recent run of any part of a day
+	pub fn latest(&self, day:u8) -> Option<&Timing> {
+		self.runs.iter().filter(|t| t.day == day).max_by_key(|t| (t.at, t.part))
+	}
+}
@@ -0,0 +1,30 @@
+// Tracing of the fetch, parse and solve phases, as a span tree or a Chrome trace
+
+use std::{io, path::Path};
+use tracing::Level;
+use tracing_chrome::{ChromeLayerBuilder, FlushGuard};
+use tracing_subscriber::{filter::Targets, layer::SubscriberExt, util::SubscriberInitExt, Registry};
+use tracing_tree::{time::Uptime, HierarchicalLayer};
+
+/// Trace the spans of this crate, as a tree on stderr when there is no file,
+/// or else into a file that chrome://tracing or Perfetto can open.
+/// The Chrome trace is only complete once the returned guard is dropped.
+pub fn init(file:Option<&Path>) -> Option<FlushGuard> {
+	let targets = Targets::new().with_target(env!("CARGO_CRATE_NAME"), Level::TRACE);
+	match file {
+		None => {
+			let tree = HierarchicalLayer::new(2)
+				.with_writer(io::stderr)
+				.with_targets(true)
+				.with_bracketed_fields(true)
+				.with_timer(Uptime::default());
+			Registry::default().with(tree).with(targets).init();
+			None
+		}
+		Some(file) => {
+			let (chrome, guard) = ChromeLayerBuilder::new().file(file).include_args(true).build();
+			Registry::default().with(chrome).with(targets).init();
+			Some(guard)
+		}
+	}
+}
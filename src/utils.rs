@@ -0,0 +1,12 @@
+// Building blocks shared by the days' solutions
+
+pub mod bits;
+pub mod cycle;
+pub mod graph;
+pub mod grid;
+pub mod math;
+pub mod parse;
+pub mod point;
+pub mod ranges;
+pub mod search;
+pub mod union_find;
@@ -0,0 +1,444 @@
+// Dense booleans, 64 to a word: sets of small numbers, and grids of flags
+
+use std::{fmt, ops::{BitAnd, BitOr, Sub}};
+
+use crate::utils::{grid::Pos, point::Point};
+
+const BITS:usize = u64::BITS as usize;
+
+fn words(bits:usize) -> usize {
+	bits.div_ceil(BITS)
+}
+
+// the indices of the bits that are set, lowest first
+fn ones(words:&[u64]) -> impl Iterator<Item = usize> + '_ {
+	words.iter().enumerate().flat_map(|(i, &word)| {
+		let mut word = word;
+		std::iter::from_fn(move || (word != 0).then(|| {
+			let bit = word.trailing_zeros() as usize;
+			word &= word - 1;
+			i * BITS + bit
+		}))
+	})
+}
+
+/// A set of small numbers, as a bit per number up to the largest one
+#[derive(Debug,Clone,Default)]
+pub struct BitSet {
+	words: Vec<u64>,
+}
+
+impl BitSet {
+
+	pub fn new() -> Self {
+		BitSet::default()
+	}
+
+	/// An empty set with room for the numbers 0..bits
+	pub fn with_capacity(bits:usize) -> Self {
+		BitSet { words: vec![0; words(bits)] }
+	}
+
+	/// Add a number, false if it was there already
+	pub fn insert(&mut self, n:usize) -> bool {
+		let (word, bit) = (n / BITS, 1 << (n % BITS));
+		if word >= self.words.len() {
+			self.words.resize(word + 1, 0);
+		}
+		let new = self.words[word] & bit == 0;
+		self.words[word] |= bit;
+		new
+	}
+
+	/// Take a number out, false if it wasn't there
+	pub fn remove(&mut self, n:usize) -> bool {
+		let (word, bit) = (n / BITS, 1 << (n % BITS));
+		match self.words.get_mut(word) {
+			Some(w) if *w & bit != 0 => { *w &= !bit; true }
+			_ => false,
+		}
+	}
+
+	pub fn contains(&self, n:usize) -> bool {
+		self.words.get(n / BITS).is_some_and(|w| w & (1 << (n % BITS)) != 0)
+	}
+
+	/// How many numbers there are
+	pub fn len(&self) -> usize {
+		self.words.iter().map(|w| w.count_ones() as usize).sum()
+	}
+
+	pub fn is_empty(&self) -> bool {
+		self.words.iter().all(|&w| w == 0)
+	}
+
+	pub fn clear(&mut self) {
+		self.words.clear();
+	}
+
+	/// The numbers, smallest first
+	pub fn iter(&self) -> impl Iterator<Item = usize> + '_ {
+		ones(&self.words)
+	}
+
+	/// How many numbers both sets have, without building their intersection
+	pub fn intersection_count(&self, other:&BitSet) -> usize {
+		self.words.iter().zip(&other.words).map(|(a, b)| (a & b).count_ones() as usize).sum()
+	}
+
+	pub fn is_disjoint(&self, other:&BitSet) -> bool {
+		self.words.iter().zip(&other.words).all(|(a, b)| a & b == 0)
+	}
+
+	pub fn union_with(&mut self, other:&BitSet) {
+		if other.words.len() > self.words.len() {
+			self.words.resize(other.words.len(), 0);
+		}
+		self.words.iter_mut().zip(&other.words).for_each(|(a, b)| *a |= b);
+	}
+
+	pub fn intersect_with(&mut self, other:&BitSet) {
+		self.words.truncate(other.words.len());
+		self.words.iter_mut().zip(&other.words).for_each(|(a, b)| *a &= b);
+	}
+
+	pub fn difference_with(&mut self, other:&BitSet) {
+		self.words.iter_mut().zip(&other.words).for_each(|(a, b)| *a &= !b);
+	}
+}
+
+// the same numbers make the same set, however many empty words either has
+impl PartialEq for BitSet {
+	fn eq(&self, other:&Self) -> bool {
+		let (short, long) = if self.words.len() <= other.words.len() { (self, other) } else { (other, self) };
+		long.words.starts_with(&short.words) && long.words[short.words.len()..].iter().all(|&w| w == 0)
+	}
+}
+
+impl Eq for BitSet {}
+
+impl FromIterator<usize> for BitSet {
+	fn from_iter<I:IntoIterator<Item = usize>>(ns:I) -> Self {
+		let mut set = BitSet::new();
+		set.extend(ns);
+		set
+	}
+}
+
+impl Extend<usize> for BitSet {
+	fn extend<I:IntoIterator<Item = usize>>(&mut self, ns:I) {
+		ns.into_iter().for_each(|n| { self.insert(n); });
+	}
+}
+
+impl BitAnd for &BitSet {
+	type Output = BitSet;
+	fn bitand(self, other:&BitSet) -> BitSet {
+		let mut set = self.clone();
+		set.intersect_with(other);
+		set
+	}
+}
+
+impl BitOr for &BitSet {
+	type Output = BitSet;
+	fn bitor(self, other:&BitSet) -> BitSet {
+		let mut set = self.clone();
+		set.union_with(other);
+		set
+	}
+}
+
+impl Sub for &BitSet {
+	type Output = BitSet;
+	fn sub(self, other:&BitSet) -> BitSet {
+		let mut set = self.clone();
+		set.difference_with(other);
+		set
+	}
+}
+
+/// A grid of flags, every row as a run of words, so whole rows shift and combine at once
+#[derive(Debug,Clone,PartialEq,Eq)]
+pub struct BitGrid {
+	width: usize,
+	height: usize,
+	// words per row
+	stride: usize,
+	words: Vec<u64>,
+}
+
+impl BitGrid {
+
+	/// All cleared
+	pub fn new(width:usize, height:usize) -> Self {
+		let stride = words(width);
+		BitGrid { width, height, stride, words: vec![0; stride * height] }
+	}
+
+	/// Set where f says so
+	pub fn from_fn(width:usize, height:usize, mut f:impl FnMut(Pos) -> bool) -> Self {
+		let mut grid = BitGrid::new(width, height);
+		for y in 0..height {
+			for x in 0..width {
+				if f((x,y)) {
+					grid.set((x,y), true);
+				}
+			}
+		}
+		grid
+	}
+
+	pub fn width(&self) -> usize {
+		self.width
+	}
+
+	pub fn height(&self) -> usize {
+		self.height
+	}
+
+	fn row(&self, y:usize) -> &[u64] {
+		&self.words[y * self.stride..(y + 1) * self.stride]
+	}
+
+	/// Whether a cell is set, cells off the grid never are
+	pub fn get(&self, (x,y):Pos) -> bool {
+		x < self.width && y < self.height && self.row(y)[x / BITS] & (1 << (x % BITS)) != 0
+	}
+
+	pub fn set(&mut self, (x,y):Pos, value:bool) {
+		assert!(x < self.width && y < self.height, "({x}, {y}) is off the {}x{} grid", self.width, self.height);
+		let (word, bit) = (y * self.stride + x / BITS, 1 << (x % BITS));
+		if value { self.words[word] |= bit } else { self.words[word] &= !bit }
+	}
+
+	/// How many cells are set
+	pub fn count(&self) -> usize {
+		self.words.iter().map(|w| w.count_ones() as usize).sum()
+	}
+
+	/// The cells that are set, row by row
+	pub fn iter(&self) -> impl Iterator<Item = Pos> + '_ {
+		(0..self.height).flat_map(move |y| ones(self.row(y)).map(move |x| (x,y)))
+	}
+
+	/// Every cell moved by a vector, those moved off the grid dropped and those left behind cleared
+	pub fn shifted(&self, by:Point) -> BitGrid {
+		let mut grid = BitGrid::new(self.width, self.height);
+		let (dx, dy) = (by.x, by.y);
+		if dx.unsigned_abs() as usize >= self.width || dy.unsigned_abs() as usize >= self.height {
+			return grid;
+		}
+		for y in 0..self.height {
+			let Some(from) = y.checked_add_signed(-dy as isize).filter(|&from| from < self.height) else {
+				continue;
+			};
+			let (src, dst) = (from * self.stride, y * self.stride);
+			shift_row(&self.words[src..src + self.stride], &mut grid.words[dst..dst + self.stride], dx as isize);
+		}
+		grid.trim();
+		grid
+	}
+
+	/// The cells next to a set cell, up, right, down or left
+	pub fn neighbours4(&self) -> BitGrid {
+		self.spread(&Point::DIRECTIONS4)
+	}
+
+	/// The cells next to a set cell, diagonals included
+	pub fn neighbours8(&self) -> BitGrid {
+		self.spread(&Point::DIRECTIONS8)
+	}
+
+	fn spread(&self, directions:&[Point]) -> BitGrid {
+		let mut grid = BitGrid::new(self.width, self.height);
+		for &d in directions {
+			grid.union_with(&self.shifted(d));
+		}
+		grid
+	}
+
+	pub fn union_with(&mut self, other:&BitGrid) {
+		self.check_size(other);
+		self.words.iter_mut().zip(&other.words).for_each(|(a, b)| *a |= b);
+	}
+
+	pub fn intersect_with(&mut self, other:&BitGrid) {
+		self.check_size(other);
+		self.words.iter_mut().zip(&other.words).for_each(|(a, b)| *a &= b);
+	}
+
+	pub fn difference_with(&mut self, other:&BitGrid) {
+		self.check_size(other);
+		self.words.iter_mut().zip(&other.words).for_each(|(a, b)| *a &= !b);
+	}
+
+	fn check_size(&self, other:&BitGrid) {
+		assert!((self.width, self.height) == (other.width, other.height),
+			"a {}x{} grid can't be combined with a {}x{} one", self.width, self.height, other.width, other.height);
+	}
+
+	// clear the bits past the width, that shifts may have put there
+	fn trim(&mut self) {
+		let used = self.width % BITS;
+		if used == 0 {
+			return;
+		}
+		let mask = (1 << used) - 1;
+		self.words.chunks_mut(self.stride).for_each(|row| row[self.stride - 1] &= mask);
+	}
+}
+
+// a row's bits moved by `by`, towards the end of the row when positive
+fn shift_row(src:&[u64], dst:&mut [u64], by:isize) {
+	let (offset, bits) = (by.unsigned_abs() / BITS, by.unsigned_abs() % BITS);
+	let word = |i:Option<usize>| i.and_then(|i| src.get(i)).copied().unwrap_or(0);
+	for (i, d) in dst.iter_mut().enumerate() {
+		*d = if by >= 0 {
+			let (from, carry) = (i.checked_sub(offset), i.checked_sub(offset + 1));
+			(word(from) << bits) | if bits > 0 { word(carry) >> (BITS - bits) } else { 0 }
+		} else {
+			let (from, carry) = (i + offset, i + offset + 1);
+			(word(Some(from)) >> bits) | if bits > 0 { word(Some(carry)) << (BITS - bits) } else { 0 }
+		};
+	}
+}
+
+impl BitAnd for &BitGrid {
+	type Output = BitGrid;
+	fn bitand(self, other:&BitGrid) -> BitGrid {
+		let mut grid = self.clone();
+		grid.intersect_with(other);
+		grid
+	}
+}
+
+impl BitOr for &BitGrid {
+	type Output = BitGrid;
+	fn bitor(self, other:&BitGrid) -> BitGrid {
+		let mut grid = self.clone();
+		grid.union_with(other);
+		grid
+	}
+}
+
+impl Sub for &BitGrid {
+	type Output = BitGrid;
+	fn sub(self, other:&BitGrid) -> BitGrid {
+		let mut grid = self.clone();
+		grid.difference_with(other);
+		grid
+	}
+}
+
+/// '#' for the cells that are set, '.' for the others
+impl fmt::Display for BitGrid {
+	fn fmt(&self, f:&mut fmt::Formatter) -> fmt::Result {
+		for y in 0..self.height {
+			for x in 0..self.width {
+				write!(f, "{}", if self.get((x,y)) { '#' } else { '.' })?;
+			}
+			writeln!(f)?;
+		}
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use std::collections::BTreeSet;
+	use pretty_assertions::assert_eq;
+	use proptest::prelude::*;
+	use super::*;
+
+	fn grid(text:&str) -> BitGrid {
+		let rows:Vec<&str> = text.lines().collect();
+		BitGrid::from_fn(rows[0].len(), rows.len(), |(x,y)| rows[y].as_bytes()[x] == b'#')
+	}
+
+	#[test]
+	fn test_set() {
+		let mut set:BitSet = [3, 64, 200, 3].into_iter().collect();
+		assert_eq!(set.len(), 3);
+		assert!(set.contains(64) && !set.contains(65) && !set.contains(10_000));
+		assert!(!set.insert(200));
+		assert!(set.remove(3) && !set.remove(3) && !set.remove(10_000));
+		assert_eq!(set.iter().collect::<Vec<_>>(), [64, 200]);
+		// empty words at the end make no difference
+		assert_eq!(&set - &[200].into_iter().collect(), [64].into_iter().collect());
+		assert_eq!(BitSet::with_capacity(1000), BitSet::new());
+	}
+
+	#[test]
+	fn test_grid() {
+		let g = grid("\
+#....
+..#..
+.....
+");
+		assert_eq!(g.count(), 2);
+		assert_eq!(g.iter().collect::<Vec<_>>(), [(0,0), (2,1)]);
+		assert_eq!(g.shifted(Point::RIGHT).to_string(), ".#...\n...#.\n.....\n");
+		assert_eq!(g.shifted(Point::UP).to_string(), "..#..\n.....\n.....\n");
+		assert_eq!(g.shifted(Point::new(-3, 1)).to_string(), ".....\n.....\n.....\n");
+		assert_eq!(g.neighbours8().to_string(), "\
+.###.
+##.#.
+.###.
+");
+		assert_eq!(g.neighbours4().to_string(), "\
+.##..
+##.#.
+..#..
+");
+	}
+
+	// a brute force model of the grid: the cells that are set
+	fn model(g:&BitGrid) -> BTreeSet<(i64,i64)> {
+		g.iter().map(|(x,y)| (x as i64, y as i64)).collect()
+	}
+
+	fn grids() -> impl Strategy<Value = BitGrid> {
+		(1usize..140, 1usize..6).prop_flat_map(|(width, height)| {
+			prop::collection::vec(any::<bool>(), width * height)
+				.prop_map(move |cells| BitGrid::from_fn(width, height, |(x,y)| cells[y * width + x]))
+		})
+	}
+
+	proptest! {
+
+		#[test]
+		fn prop_set(a in prop::collection::btree_set(0usize..300, 0..40), b in prop::collection::btree_set(0usize..300, 0..40)) {
+			let (sa, sb):(BitSet,BitSet) = (a.iter().copied().collect(), b.iter().copied().collect());
+			prop_assert_eq!(sa.iter().collect::<BTreeSet<_>>(), a.clone());
+			prop_assert_eq!((&sa & &sb).iter().collect::<BTreeSet<_>>(), &a & &b);
+			prop_assert_eq!((&sa | &sb).iter().collect::<BTreeSet<_>>(), &a | &b);
+			prop_assert_eq!((&sa - &sb).iter().collect::<BTreeSet<_>>(), &a - &b);
+			prop_assert_eq!(sa.intersection_count(&sb), a.intersection(&b).count());
+			prop_assert_eq!(sa.is_disjoint(&sb), a.is_disjoint(&b));
+		}
+
+		#[test]
+		fn prop_shifted(g in grids(), dx in -70i64..70, dy in -6i64..6) {
+			let (w, h) = (g.width() as i64, g.height() as i64);
+			let expected:BTreeSet<(i64,i64)> = model(&g).into_iter()
+				.map(|(x,y)| (x + dx, y + dy))
+				.filter(|&(x,y)| (0..w).contains(&x) && (0..h).contains(&y))
+				.collect();
+			prop_assert_eq!(model(&g.shifted(Point::new(dx, dy))), expected);
+		}
+
+		#[test]
+		fn prop_neighbours(g in grids()) {
+			let cells = model(&g);
+			let near = g.neighbours8();
+			for y in 0..g.height() {
+				for x in 0..g.width() {
+					let p = Point::from((x,y));
+					let expected = Point::DIRECTIONS8.iter().any(|&d| cells.contains(&((p - d).x, (p - d).y)));
+					prop_assert_eq!(near.get((x,y)), expected);
+				}
+			}
+		}
+	}
+}
@@ -0,0 +1,160 @@
+// Cycles in iterated functions: x, f(x), f(f(x)), ... always ends up going round, when there are finitely many states
+
+use std::{collections::HashMap, hash::Hash};
+
+/// Where the iterates of a state start going round, and how many states round they go
+#[derive(Debug,Clone,Copy,PartialEq,Eq)]
+pub struct Cycle {
+	/// iterations before the first state on the cycle
+	pub start: usize,
+	pub length: usize,
+}
+
+impl Cycle {
+	/// The smallest iteration count landing on the same state as `n` iterations
+	pub fn reduce(&self, n:usize) -> usize {
+		if n < self.start { n } else { self.start + (n - self.start) % self.length }
+	}
+}
+
+/// With Brent's algorithm, which only keeps two states around and calls f the fewest times
+pub fn brent<S:Clone + PartialEq>(initial:S, f:impl Fn(&S) -> S) -> Cycle {
+
+	// the length first, looking for the hare from the tortoise in ever doubling windows
+	let (mut power, mut length) = (1, 1);
+	let mut tortoise = initial.clone();
+	let mut hare = f(&initial);
+	while tortoise != hare {
+		if power == length {
+			tortoise = hare.clone();
+			power *= 2;
+			length = 0;
+		}
+		hare = f(&hare);
+		length += 1;
+	}
+
+	// then the start, with the hare a length ahead
+	let mut tortoise = initial.clone();
+	let mut hare = (0..length).fold(initial, |s, _| f(&s));
+	let mut start = 0;
+	while tortoise != hare {
+		tortoise = f(&tortoise);
+		hare = f(&hare);
+		start += 1;
+	}
+
+	Cycle { start, length }
+}
+
+/// With Floyd's tortoise and hare, which only keeps two states around
+pub fn floyd<S:Clone + PartialEq>(initial:S, f:impl Fn(&S) -> S) -> Cycle {
+
+	let mut tortoise = f(&initial);
+	let mut hare = f(&tortoise);
+	while tortoise != hare {
+		tortoise = f(&tortoise);
+		hare = f(&f(&hare));
+	}
+
+	let mut start = 0;
+	tortoise = initial;
+	while tortoise != hare {
+		tortoise = f(&tortoise);
+		hare = f(&hare);
+		start += 1;
+	}
+
+	let mut length = 1;
+	hare = f(&tortoise);
+	while tortoise != hare {
+		hare = f(&hare);
+		length += 1;
+	}
+
+	Cycle { start, length }
+}
+
+/// By remembering every state, which calls f the fewest times and keeps the states it saw, in order
+pub fn find<S:Clone + Eq + Hash>(initial:S, f:impl Fn(&S) -> S) -> (Cycle,Vec<S>) {
+	let mut seen = HashMap::new();
+	let mut states = vec![];
+	let mut state = initial;
+	loop {
+		if let Some(&start) = seen.get(&state) {
+			return (Cycle { start, length: states.len() - start }, states);
+		}
+		seen.insert(state.clone(), states.len());
+		states.push(state.clone());
+		state = f(&state);
+	}
+}
+
+/// The state after `n` iterations, however many: iterating up to there,
+/// or only until going round once when the cycle closes first
+pub fn nth<S:Clone + Eq + Hash>(initial:S, f:impl Fn(&S) -> S, n:usize) -> S {
+	let mut seen = HashMap::new();
+	let mut states = vec![];
+	let mut state = initial;
+	while states.len() < n {
+		if let Some(&start) = seen.get(&state) {
+			let cycle = Cycle { start, length: states.len() - start };
+			return states.swap_remove(cycle.reduce(n));
+		}
+		seen.insert(state.clone(), states.len());
+		states.push(state.clone());
+		state = f(&state);
+	}
+	state
+}
+
+#[cfg(test)]
+mod tests {
+	use pretty_assertions::assert_eq;
+	use super::*;
+
+	#[test]
+	fn test_cycles() {
+		// every linear congruential function on small moduli, from every state
+		for m in 1..=12u64 {
+			for a in 0..m {
+				for b in 0..m {
+					let f = |x:&u64| (a * x + b) % m;
+					for x in 0..m {
+						let states:Vec<u64> = std::iter::successors(Some(x), |x| Some(f(x))).take(2 * m as usize + 1).collect();
+						let start = (0..).find(|&i| states[i + 1..].contains(&states[i])).unwrap();
+						let length = (1..).find(|&l| states[start + l] == states[start]).unwrap();
+						let expected = Cycle { start, length };
+
+						assert_eq!(brent(x, f), expected, "brent for {a}x + {b} mod {m} from {x}");
+						assert_eq!(floyd(x, f), expected, "floyd for {a}x + {b} mod {m} from {x}");
+						assert_eq!(find(x, f).0, expected, "find for {a}x + {b} mod {m} from {x}");
+						for (n, &state) in states.iter().enumerate() {
+							assert_eq!(nth(x, f, n), state);
+						}
+					}
+				}
+			}
+		}
+	}
+
+	#[test]
+	fn test_nth() {
+		let f = |x:&u64| (x * x + 1) % 1_000_003;
+		let n = 1_000_000_000;
+		let cycle = brent(2, f);
+		let expected = (0..cycle.reduce(n)).fold(2, |x, _| f(&x));
+		assert_eq!(nth(2, f, n), expected);
+
+		// no further than asked, when that's before going round: this one never does
+		assert_eq!(nth(0, |x:&u64| x + 1, 3), 3);
+		assert_eq!(nth(7, |x:&u64| x + 1, 0), 7);
+
+		// a walk through a table of next nodes, following repeating instructions, goes round once they line up
+		let next = [[1, 1], [0, 2], [2, 2]];
+		let instructions = [0, 0, 1];
+		let step = |&(node, i):&(usize,usize)| (next[node][instructions[i]], (i + 1) % instructions.len());
+		assert_eq!(brent((0, 0), step), Cycle { start: 6, length: 3 });
+		assert_eq!(nth((0, 0), step, n).0, 2);
+	}
+}
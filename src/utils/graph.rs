@@ -0,0 +1,243 @@
+// Directed graphs of labelled nodes, and the usual ways through them
+
+use std::{collections::{HashMap, VecDeque}, hash::Hash};
+
+/// A node, by the order it was added in
+pub type NodeId = usize;
+
+#[derive(Debug,Clone)]
+pub struct Graph<L> {
+	labels: Vec<L>,
+	ids: HashMap<L,NodeId>,
+	edges: Vec<Vec<NodeId>>,
+}
+
+impl<L> Default for Graph<L> {
+	fn default() -> Self {
+		Graph { labels: vec![], ids: HashMap::new(), edges: vec![] }
+	}
+}
+
+impl<L:Clone + Eq + Hash> Graph<L> {
+
+	pub fn new() -> Self {
+		Graph::default()
+	}
+
+	/// The node with this label, added if there isn't one yet
+	pub fn add_node(&mut self, label:L) -> NodeId {
+		if let Some(&id) = self.ids.get(&label) {
+			return id;
+		}
+		let id = self.labels.len();
+		self.labels.push(label.clone());
+		self.ids.insert(label, id);
+		self.edges.push(vec![]);
+		id
+	}
+
+	/// An edge between the nodes with these labels, adding them if needed.
+	/// Edges from a node keep the order they were added in.
+	pub fn add_edge(&mut self, from:L, to:L) -> (NodeId,NodeId) {
+		let (from, to) = (self.add_node(from), self.add_node(to));
+		self.edges[from].push(to);
+		(from, to)
+	}
+
+	pub fn node(&self, label:&L) -> Option<NodeId> {
+		self.ids.get(label).copied()
+	}
+}
+
+impl<L> Graph<L> {
+
+	pub fn len(&self) -> usize {
+		self.labels.len()
+	}
+
+	pub fn is_empty(&self) -> bool {
+		self.labels.is_empty()
+	}
+
+	pub fn label(&self, id:NodeId) -> &L {
+		&self.labels[id]
+	}
+
+	pub fn nodes(&self) -> impl Iterator<Item = (NodeId,&L)> {
+		self.labels.iter().enumerate()
+	}
+
+	/// Where the edges from a node go, in the order they were added
+	pub fn successors(&self, id:NodeId) -> &[NodeId] {
+		&self.edges[id]
+	}
+
+	/// The nodes reachable from `start`, nearest first
+	pub fn bfs(&self, start:NodeId) -> Vec<NodeId> {
+		let distances = self.distances(start);
+		let mut order:Vec<NodeId> = (0..self.len()).filter(|&id| distances[id].is_some()).collect();
+		order.sort_by_key(|&id| distances[id]);
+		order
+	}
+
+	/// How many edges away from `start` every node is, if it can be reached at all
+	pub fn distances(&self, start:NodeId) -> Vec<Option<usize>> {
+		let mut distances = vec![None; self.len()];
+		distances[start] = Some(0);
+		let mut queue = VecDeque::from([start]);
+		while let Some(id) = queue.pop_front() {
+			let next = distances[id].map(|d| d + 1);
+			for &to in &self.edges[id] {
+				if distances[to].is_none() {
+					distances[to] = next;
+					queue.push_back(to);
+				}
+			}
+		}
+		distances
+	}
+
+	/// The nodes reachable from `start`, going as deep as possible first, in preorder
+	pub fn dfs(&self, start:NodeId) -> Vec<NodeId> {
+		let mut seen = vec![false; self.len()];
+		let mut order = vec![];
+		let mut stack = vec![start];
+		while let Some(id) = stack.pop() {
+			if std::mem::replace(&mut seen[id], true) {
+				continue;
+			}
+			order.push(id);
+			// reversed, so that the first edge is followed first
+			stack.extend(self.edges[id].iter().rev().filter(|&&to| !seen[to]));
+		}
+		order
+	}
+
+	/// Which nodes can be reached from `start`, itself included
+	pub fn reachable(&self, start:NodeId) -> Vec<bool> {
+		self.distances(start).iter().map(Option::is_some).collect()
+	}
+
+	pub fn reaches(&self, from:NodeId, to:NodeId) -> bool {
+		self.distances(from)[to].is_some()
+	}
+
+	/// The nodes, each before the ones its edges go to, unless there's a cycle
+	pub fn topological_sort(&self) -> Option<Vec<NodeId>> {
+		let mut incoming = vec![0; self.len()];
+		self.edges.iter().flatten().for_each(|&to| incoming[to] += 1);
+		let mut ready:VecDeque<NodeId> = (0..self.len()).filter(|&id| incoming[id] == 0).collect();
+		let mut order = Vec::with_capacity(self.len());
+		while let Some(id) = ready.pop_front() {
+			order.push(id);
+			for &to in &self.edges[id] {
+				incoming[to] -= 1;
+				if incoming[to] == 0 {
+					ready.push_back(to);
+				}
+			}
+		}
+		(order.len() == self.len()).then_some(order)
+	}
+
+	/// The groups of nodes that can all reach each other, with Tarjan's algorithm.
+	/// A group comes before the groups it has edges to.
+	pub fn strongly_connected_components(&self) -> Vec<Vec<NodeId>> {
+
+		const UNVISITED:usize = usize::MAX;
+
+		let mut index = vec![UNVISITED; self.len()];
+		let mut lowlink = vec![0; self.len()];
+		let mut on_stack = vec![false; self.len()];
+		let mut stack = vec![];
+		let mut components = vec![];
+		let mut next = 0;
+
+		for root in 0..self.len() {
+			if index[root] != UNVISITED {
+				continue;
+			}
+			// (node, how many of its edges were followed), instead of recursing
+			let mut calls = vec![(root, 0)];
+			while let Some(&mut (id, ref mut edge)) = calls.last_mut() {
+				if *edge == 0 && index[id] == UNVISITED {
+					index[id] = next;
+					lowlink[id] = next;
+					next += 1;
+					stack.push(id);
+					on_stack[id] = true;
+				}
+				if let Some(&to) = self.edges[id].get(*edge) {
+					*edge += 1;
+					if index[to] == UNVISITED {
+						calls.push((to, 0));
+					} else if on_stack[to] {
+						lowlink[id] = lowlink[id].min(index[to]);
+					}
+					continue;
+				}
+				calls.pop();
+				if let Some(&(parent, _)) = calls.last() {
+					lowlink[parent] = lowlink[parent].min(lowlink[id]);
+				}
+				if lowlink[id] == index[id] {
+					let mut component = vec![];
+					while let Some(member) = stack.pop() {
+						on_stack[member] = false;
+						component.push(member);
+						if member == id {
+							break;
+						}
+					}
+					components.push(component);
+				}
+			}
+		}
+
+		// Tarjan's finds them in reverse topological order
+		components.reverse();
+		components
+	}
+}
+
+impl<L:Clone + Eq + Hash> FromIterator<(L,L)> for Graph<L> {
+	fn from_iter<I:IntoIterator<Item = (L,L)>>(edges:I) -> Self {
+		let mut graph = Graph::new();
+		edges.into_iter().for_each(|(from, to)| { graph.add_edge(from, to); });
+		graph
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use pretty_assertions::assert_eq;
+	use super::*;
+
+	fn labels<'a>(graph:&Graph<&'a str>, ids:&[NodeId]) -> Vec<&'a str> {
+		ids.iter().map(|&id| *graph.label(id)).collect()
+	}
+
+	#[test]
+	fn test_walks() {
+		let graph:Graph<&str> = [("a","b"), ("a","c"), ("b","d"), ("c","d"), ("d","e"), ("f","a")].into_iter().collect();
+		let a = graph.node(&"a").unwrap();
+		assert_eq!(graph.len(), 6);
+		assert_eq!(labels(&graph, graph.successors(a)), ["b","c"]);
+		assert_eq!(labels(&graph, &graph.bfs(a)), ["a","b","c","d","e"]);
+		assert_eq!(labels(&graph, &graph.dfs(a)), ["a","b","d","e","c"]);
+		assert_eq!(graph.distances(a)[graph.node(&"e").unwrap()], Some(3));
+		assert!(!graph.reaches(a, graph.node(&"f").unwrap()));
+		assert_eq!(graph.reachable(a).iter().filter(|&&r| r).count(), 5);
+		assert_eq!(labels(&graph, &graph.topological_sort().unwrap()), ["f","a","b","c","d","e"]);
+	}
+
+	#[test]
+	fn test_cycles() {
+		let graph:Graph<u8> = [(1,2), (2,3), (3,1), (3,4), (4,5), (5,4), (6,6)].into_iter().collect();
+		assert_eq!(graph.topological_sort(), None);
+		let components:Vec<Vec<u8>> = graph.strongly_connected_components().iter()
+			.map(|c| { let mut c:Vec<u8> = c.iter().map(|&id| *graph.label(id)).collect(); c.sort(); c })
+			.collect();
+		assert_eq!(components, [vec![6], vec![1,2,3], vec![4,5]]);
+	}
+}
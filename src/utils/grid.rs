@@ -0,0 +1,208 @@
+// A rectangle of cells, as many puzzles come as
+
+use std::{fmt, ops::{Index, IndexMut}};
+
+use super::point::{Point, Vec2};
+
+/// A position in a grid, as (x, y) from the top left
+pub type Pos = (usize, usize);
+
+#[derive(Debug,Clone,PartialEq,Eq,Hash)]
+pub struct Grid<T> {
+	width: usize,
+	height: usize,
+	cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+
+	/// A grid from its rows, one after the other
+	pub fn new(width:usize, cells:Vec<T>) -> Self {
+		let height = cells.len().checked_div(width).unwrap_or(0);
+		assert_eq!(width * height, cells.len(), "{} cells don't make rows of {width}", cells.len());
+		Grid { width, height, cells }
+	}
+
+	/// A grid from lines of text, mapping every char to a cell.
+	/// Panics when the lines aren't all as long.
+	pub fn parse(text:&str, mut f:impl FnMut(char) -> T) -> Self {
+		let mut width = None;
+		let mut cells = vec![];
+		for (y, line) in text.lines().enumerate() {
+			let len = cells.len();
+			cells.extend(line.chars().map(&mut f));
+			let w = *width.get_or_insert(cells.len() - len);
+			assert_eq!(cells.len() - len, w, "row {y} should be {w} wide");
+		}
+		Grid::new(width.unwrap_or(0), cells)
+	}
+
+	pub fn width(&self) -> usize {
+		self.width
+	}
+
+	pub fn height(&self) -> usize {
+		self.height
+	}
+
+	pub fn contains(&self, (x,y):Pos) -> bool {
+		x < self.width && y < self.height
+	}
+
+	pub fn get(&self, pos:Pos) -> Option<&T> {
+		self.contains(pos).then(|| &self.cells[pos.1 * self.width + pos.0])
+	}
+
+	pub fn get_mut(&mut self, pos:Pos) -> Option<&mut T> {
+		self.contains(pos).then(|| &mut self.cells[pos.1 * self.width + pos.0])
+	}
+
+	/// The cell at a point, unless it's off the grid
+	pub fn at(&self, p:Point) -> Option<&T> {
+		self.get(Pos::try_from(p).ok()?)
+	}
+
+	/// The positions next to one, up, right, down then left, within the grid
+	pub fn neighbours4(&self, pos:Pos) -> impl Iterator<Item = Pos> + '_ {
+		self.around(pos, &Point::DIRECTIONS4)
+	}
+
+	/// The positions around one, diagonals included, clockwise from the top left, within the grid
+	pub fn neighbours8(&self, pos:Pos) -> impl Iterator<Item = Pos> + '_ {
+		self.around(pos, &Point::DIRECTIONS8)
+	}
+
+	fn around<'a>(&'a self, pos:Pos, directions:&'a [Vec2]) -> impl Iterator<Item = Pos> + 'a {
+		let p = Point::from(pos);
+		directions.iter()
+			.filter_map(move |&d| Pos::try_from(p + d).ok())
+			.filter(|&pos| self.contains(pos))
+	}
+
+	pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+		self.cells.chunks(self.width.max(1))
+	}
+
+	pub fn row(&self, y:usize) -> &[T] {
+		assert!(y < self.height, "row {y} is out of the grid");
+		&self.cells[y * self.width..(y + 1) * self.width]
+	}
+
+	pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+		(0..self.width).map(|x| self.column(x))
+	}
+
+	pub fn column(&self, x:usize) -> impl DoubleEndedIterator<Item = &T> {
+		assert!(x < self.width, "column {x} is out of the grid");
+		self.cells.iter().skip(x).step_by(self.width)
+	}
+
+	/// Every cell, with its position, row by row
+	pub fn iter(&self) -> impl Iterator<Item = (Pos, &T)> {
+		self.cells.iter().enumerate().map(|(i, cell)| ((i % self.width, i / self.width), cell))
+	}
+
+	/// The positions of the cells that match, row by row
+	pub fn positions<'a>(&'a self, mut f:impl FnMut(&T) -> bool + 'a) -> impl Iterator<Item = Pos> + 'a {
+		self.iter().filter(move |(_, cell)| f(cell)).map(|(pos, _)| pos)
+	}
+
+	/// The position of the first cell with that value, row by row
+	pub fn find(&self, value:&T) -> Option<Pos> where T:PartialEq {
+		self.positions(|cell| cell == value).next()
+	}
+
+	pub fn map<U>(&self, f:impl FnMut(&T) -> U) -> Grid<U> {
+		Grid { width: self.width, height: self.height, cells: self.cells.iter().map(f).collect() }
+	}
+
+	/// Rows as columns, and columns as rows
+	pub fn transpose(&self) -> Self where T:Clone {
+		let cells = (0..self.width).flat_map(|x| self.column(x).cloned()).collect();
+		Grid::new(self.height, cells)
+	}
+
+	/// Turned a quarter clockwise
+	pub fn rotate(&self) -> Self where T:Clone {
+		let cells = (0..self.width).flat_map(|x| self.column(x).rev().cloned()).collect();
+		Grid::new(self.height, cells)
+	}
+}
+
+impl<T> Index<Pos> for Grid<T> {
+	type Output = T;
+	fn index(&self, pos:Pos) -> &T {
+		self.get(pos).unwrap_or_else(|| panic!("{pos:?} is out of the grid"))
+	}
+}
+
+impl<T> IndexMut<Pos> for Grid<T> {
+	fn index_mut(&mut self, pos:Pos) -> &mut T {
+		self.get_mut(pos).unwrap_or_else(|| panic!("{pos:?} is out of the grid"))
+	}
+}
+
+/// Back to text, a line per row
+impl<T:fmt::Display> fmt::Display for Grid<T> {
+	fn fmt(&self, f:&mut fmt::Formatter) -> fmt::Result {
+		for row in self.rows() {
+			row.iter().try_for_each(|cell| write!(f, "{cell}"))?;
+			writeln!(f)?;
+		}
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use pretty_assertions::{assert_eq, assert_str_eq};
+	use super::*;
+
+	const TEXT:&str = "ab#\n.#c\n";
+
+	#[test]
+	fn test_parse() {
+		let grid = Grid::parse(TEXT, |c| c);
+		assert_eq!((grid.width(), grid.height()), (3, 2));
+		assert_eq!(grid[(2,1)], 'c');
+		assert_eq!(grid.get((3,0)), None);
+		assert_eq!(grid.get((0,2)), None);
+		assert_eq!(grid.at(Point::new(1,1)), Some(&'#'));
+		assert_eq!(grid.at(Point::new(-1,0)), None);
+		assert_eq!(grid.find(&'#'), Some((2,0)));
+		assert_eq!(grid.positions(|&c| c == '#').collect::<Vec<_>>(), [(2,0),(1,1)]);
+		assert_str_eq!(grid.to_string(), TEXT);
+
+		let empty = Grid::parse("", |c| c);
+		assert_eq!((empty.width(), empty.height(), empty.rows().count()), (0, 0, 0));
+	}
+
+	#[test]
+	#[should_panic(expected = "row 1 should be 3 wide")]
+	fn test_parse_ragged() {
+		Grid::parse("abc\nde\n", |c| c);
+	}
+
+	#[test]
+	fn test_neighbours() {
+		let grid = Grid::new(3, vec![0;9]);
+		assert_eq!(grid.neighbours4((1,1)).collect::<Vec<_>>(), [(1,0),(2,1),(1,2),(0,1)]);
+		assert_eq!(grid.neighbours4((0,0)).collect::<Vec<_>>(), [(1,0),(0,1)]);
+		assert_eq!(grid.neighbours8((1,1)).count(), 8);
+		assert_eq!(grid.neighbours8((2,2)).collect::<Vec<_>>(), [(1,1),(2,1),(1,2)]);
+	}
+
+	#[test]
+	fn test_views() {
+		let mut grid = Grid::parse(TEXT, |c| c);
+		assert_eq!(grid.row(1), ['.','#','c']);
+		assert_eq!(grid.column(2).collect::<String>(), "#c");
+		assert_eq!(grid.columns().map(|c| c.collect::<String>()).collect::<Vec<_>>(), ["a.", "b#", "#c"]);
+		assert_str_eq!(grid.transpose().to_string(), "a.\nb#\n#c\n");
+		assert_str_eq!(grid.rotate().to_string(), ".a\n#b\nc#\n");
+		assert_eq!(grid.rotate().rotate().rotate().rotate(), grid);
+		assert_str_eq!(grid.map(|c| c.is_alphabetic() as u8).to_string(), "110\n001\n");
+		grid[(0,1)] = 'd';
+		assert_eq!(grid.iter().nth(3), Some(((0,1), &'d')));
+	}
+}
@@ -0,0 +1,148 @@
+// Number theory: gcd and lcm, modular arithmetic, and the Chinese remainder theorem
+
+pub fn gcd(a:u64, b:u64) -> u64 {
+	if b == 0 { a } else { gcd(b, a % b) }
+}
+
+/// None when it doesn't fit in a u64
+pub fn lcm(a:u64, b:u64) -> Option<u64> {
+	if a == 0 || b == 0 {
+		return Some(0);
+	}
+	(a / gcd(a, b)).checked_mul(b)
+}
+
+/// 0 for no numbers
+pub fn gcd_all(ns:&[u64]) -> u64 {
+	ns.iter().fold(0, |g, &n| gcd(g, n))
+}
+
+/// 1 for no numbers, None when it doesn't fit in a u64
+pub fn lcm_all(ns:&[u64]) -> Option<u64> {
+	ns.iter().try_fold(1, |l, &n| lcm(l, n))
+}
+
+/// (g, x, y) such that a·x + b·y = g = gcd(a, b), with g non-negative
+pub fn extended_gcd(a:i64, b:i64) -> (i64,i64,i64) {
+	let (mut r0, mut r1) = (a, b);
+	let (mut x0, mut x1) = (1, 0);
+	let (mut y0, mut y1) = (0, 1);
+	while r1 != 0 {
+		let q = r0 / r1;
+		(r0, r1) = (r1, r0 - q * r1);
+		(x0, x1) = (x1, x0 - q * x1);
+		(y0, y1) = (y1, y0 - q * y1);
+	}
+	if r0 < 0 { (-r0, -x0, -y0) } else { (r0, x0, y0) }
+}
+
+/// x in 0..m such that a·x ≡ 1 (mod m), if a and m are coprime
+pub fn mod_inverse(a:i64, m:i64) -> Option<i64> {
+	assert!(m > 0, "modulus {m} isn't positive");
+	let (g, x, _) = extended_gcd(a.rem_euclid(m), m);
+	(g == 1).then(|| x.rem_euclid(m))
+}
+
+/// base^exp mod m, by squaring
+pub fn mod_pow(base:u64, mut exp:u64, m:u64) -> u64 {
+	assert!(m > 0, "modulus {m} isn't positive");
+	let m = u128::from(m);
+	let mut base = u128::from(base) % m;
+	let mut result = 1 % m;
+	while exp > 0 {
+		if exp & 1 == 1 {
+			result = result * base % m;
+		}
+		base = base * base % m;
+		exp >>= 1;
+	}
+	result.try_into().unwrap()
+}
+
+/// The x ≡ residue (mod modulus) for all the congruences, as (x, lcm of the moduli) with x in 0..lcm.
+/// The moduli don't have to be coprime; None when the congruences contradict each other,
+/// or the lcm doesn't fit in an i64.
+pub fn crt(congruences:&[(i64,i64)]) -> Option<(i64,i64)> {
+	congruences.iter().try_fold((0, 1), |(x, m), &(r, n)| {
+		assert!(n > 0, "modulus {n} isn't positive");
+		let r = r.rem_euclid(n);
+		// x + m·k ≡ r (mod n), solvable for k only if gcd(m, n) divides r - x
+		let (g, p, _) = extended_gcd(m, n);
+		let diff = r - x;
+		if diff % g != 0 {
+			return None;
+		}
+		let step = n / g;
+		let k = (i128::from(diff / g) * i128::from(p)).rem_euclid(i128::from(step));
+		let l = m.checked_mul(step)?;
+		let x = (i128::from(x) + i128::from(m) * k).rem_euclid(i128::from(l));
+		Some((x.try_into().unwrap(), l))
+	})
+}
+
+#[cfg(test)]
+mod tests {
+	use pretty_assertions::assert_eq;
+	use super::*;
+
+	const N:u64 = 40;
+
+	#[test]
+	fn test_gcd_lcm() {
+		for a in 0..=N {
+			for b in 0..=N {
+				let g = (1..=a.max(b)).rev().find(|d| a % d == 0 && b % d == 0).unwrap_or(0);
+				let l = if a == 0 || b == 0 { 0 } else { (1..=a*b).find(|l| l % a == 0 && l % b == 0).unwrap() };
+				assert_eq!(gcd(a, b), g, "gcd({a}, {b})");
+				assert_eq!(lcm(a, b), Some(l), "lcm({a}, {b})");
+				assert_eq!(gcd_all(&[a, b]), g);
+				assert_eq!(lcm_all(&[a, b]), Some(l));
+
+				let (ia, ib) = (a as i64 - 20, b as i64 - 20);
+				let (g, x, y) = extended_gcd(ia, ib);
+				assert_eq!(ia * x + ib * y, g, "extended_gcd({ia}, {ib})");
+				assert_eq!(g as u64, gcd(ia.unsigned_abs(), ib.unsigned_abs()));
+			}
+		}
+		assert_eq!(gcd_all(&[]), 0);
+		assert_eq!(lcm_all(&[]), Some(1));
+		assert_eq!(lcm_all(&[12, 18, 30]), Some(180));
+		assert_eq!(lcm(u64::MAX, u64::MAX - 1), None);
+	}
+
+	#[test]
+	fn test_modular() {
+		for m in 1..=N as i64 {
+			for a in -(N as i64)..=N as i64 {
+				let inverse = (0..m).find(|x| (a * x).rem_euclid(m) == 1 % m);
+				assert_eq!(mod_inverse(a, m), inverse, "mod_inverse({a}, {m})");
+			}
+			for base in 0..=N {
+				for exp in 0..8 {
+					let power = (0..exp).fold(1 % m as u64, |p, _| p * base % m as u64);
+					assert_eq!(mod_pow(base, exp, m as u64), power, "mod_pow({base}, {exp}, {m})");
+				}
+			}
+		}
+		assert_eq!(mod_pow(u64::MAX, u64::MAX, u64::MAX - 1), 1);
+	}
+
+	#[test]
+	fn test_crt() {
+		for m in 1..=12 {
+			for n in 1..=12 {
+				for r in 0..m {
+					for s in 0..n {
+						let l = lcm(m as u64, n as u64).unwrap() as i64;
+						let x = (0..l).find(|x| x % m == r && x % n == s);
+						assert_eq!(crt(&[(r, m), (s, n)]), x.map(|x| (x, l)), "x ≡ {r} (mod {m}), x ≡ {s} (mod {n})");
+					}
+				}
+			}
+		}
+		assert_eq!(crt(&[]), Some((0, 1)));
+		assert_eq!(crt(&[(-1, 7)]), Some((6, 7)));
+		assert_eq!(crt(&[(2, 3), (3, 5), (2, 7)]), Some((23, 105)));
+		assert_eq!(crt(&[(1, i64::MAX), (0, 2)]), None);
+	}
+}
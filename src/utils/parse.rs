@@ -0,0 +1,351 @@
+// A small toolkit to parse puzzle inputs, which fails saying where and what it expected
+
+use std::{fmt, str::FromStr};
+
+use super::grid::Grid;
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+#[derive(Debug,Clone,PartialEq,Eq)]
+pub enum Expected {
+	/// Digits, maybe after a minus sign, that fit the type
+	Number,
+	/// Letters, digits or underscores
+	Identifier,
+	Literal(&'static str),
+	OneOf(Vec<&'static str>),
+	EndOfLine,
+	End,
+	/// A grid row as wide as the first one
+	Width(usize),
+	/// A char that stands for a grid cell
+	Cell,
+	/// Something only the caller knows how to check, in its words
+	Other(&'static str),
+}
+
+impl fmt::Display for Expected {
+	fn fmt(&self, f:&mut fmt::Formatter) -> fmt::Result {
+		match self {
+			Expected::Number => f.write_str("a number"),
+			Expected::Identifier => f.write_str("an identifier"),
+			Expected::Literal(s) => write!(f, "{s:?}"),
+			Expected::OneOf(names) => write!(f, "one of {}", names.join(", ")),
+			Expected::EndOfLine => f.write_str("the end of the line"),
+			Expected::End => f.write_str("the end of the input"),
+			Expected::Width(width) => write!(f, "a row {width} wide"),
+			Expected::Cell => f.write_str("a grid cell"),
+			Expected::Other(what) => f.write_str(what),
+		}
+	}
+}
+
+/// What was expected, where, and what was there instead
+#[derive(Debug,Clone,PartialEq,Eq)]
+pub struct Error {
+	pub line: usize,
+	pub column: usize,
+	pub expected: Expected,
+	pub found: String,
+}
+
+impl fmt::Display for Error {
+	fn fmt(&self, f:&mut fmt::Formatter) -> fmt::Result {
+		write!(f, "line {}, column {}: expected {}, found {}", self.line, self.column, self.expected, self.found)
+	}
+}
+
+impl std::error::Error for Error {}
+
+/// A position in a text being parsed, which parsers move forward
+#[derive(Debug,Clone)]
+pub struct Cursor<'a> {
+	text: &'a str,
+	offset: usize,
+}
+
+impl<'a> Cursor<'a> {
+
+	pub fn new(text:&'a str) -> Self {
+		Cursor { text, offset: 0 }
+	}
+
+	/// What is left to parse
+	pub fn rest(&self) -> &'a str {
+		&self.text[self.offset..]
+	}
+
+	pub fn is_empty(&self) -> bool {
+		self.rest().is_empty()
+	}
+
+	/// An error at this position
+	pub fn error(&self, expected:Expected) -> Error {
+		let before = &self.text[..self.offset];
+		let line = before.matches('\n').count() + 1;
+		let column = before.rsplit('\n').next().unwrap_or_default().chars().count() + 1;
+		let rest = self.rest().lines().next().unwrap_or_default();
+		let found = match rest.chars().count() {
+			_ if self.is_empty() => "the end of the input".into(),
+			0 => "the end of the line".into(),
+			n if n > 20 => format!("{:?}...", rest.chars().take(20).collect::<String>()),
+			_ => format!("{rest:?}"),
+		};
+		Error { line, column, expected, found }
+	}
+
+	fn advance(&mut self, len:usize) -> &'a str {
+		let taken = &self.rest()[..len];
+		self.offset += len;
+		taken
+	}
+
+	/// The chars for which `f` holds, maybe none
+	pub fn take_while(&mut self, mut f:impl FnMut(char) -> bool) -> &'a str {
+		let len = self.rest().find(|c| !f(c)).unwrap_or(self.rest().len());
+		self.advance(len)
+	}
+
+	/// Spaces or tabs, maybe none
+	pub fn spaces(&mut self) {
+		self.take_while(|c| c == ' ' || c == '\t');
+	}
+
+	pub fn tag(&mut self, literal:&'static str) -> Result<()> {
+		if !self.rest().starts_with(literal) {
+			return Err(self.error(Expected::Literal(literal)));
+		}
+		self.advance(literal.len());
+		Ok(())
+	}
+
+	pub fn number<T:FromStr>(&mut self) -> Result<T> {
+		let start = self.clone();
+		let sign = usize::from(self.rest().starts_with('-'));
+		let digits = self.rest()[sign..].find(|c:char| !c.is_ascii_digit()).unwrap_or(self.rest().len() - sign);
+		match self.rest()[..sign + digits].parse() {
+			Ok(n) if digits > 0 => {
+				self.advance(sign + digits);
+				Ok(n)
+			}
+			_ => Err(start.error(Expected::Number)),
+		}
+	}
+
+	pub fn identifier(&mut self) -> Result<&'a str> {
+		match self.take_while(|c| c.is_alphanumeric() || c == '_') {
+			"" => Err(self.error(Expected::Identifier)),
+			name => Ok(name),
+		}
+	}
+
+	/// The value of the first name that comes next
+	pub fn one_of<T:Clone>(&mut self, choices:&[(&'static str, T)]) -> Result<T> {
+		match choices.iter().find(|(name, _)| self.rest().starts_with(name)) {
+			Some((name, value)) => {
+				self.advance(name.len());
+				Ok(value.clone())
+			}
+			None => Err(self.error(Expected::OneOf(choices.iter().map(|(name, _)| *name).collect()))),
+		}
+	}
+
+	/// One item or more, between separators
+	pub fn list<T>(&mut self, separator:&'static str, mut item:impl FnMut(&mut Self) -> Result<T>) -> Result<Vec<T>> {
+		let mut items = vec![item(self)?];
+		while self.rest().starts_with(separator) {
+			self.advance(separator.len());
+			items.push(item(self)?);
+		}
+		Ok(items)
+	}
+
+	/// One number or more, after and between any number of spaces, like in aligned columns
+	pub fn numbers<T:FromStr>(&mut self) -> Result<Vec<T>> {
+		self.spaces();
+		let mut numbers = vec![self.number()?];
+		loop {
+			let mut next = self.clone();
+			next.spaces();
+			// failing costs as much as counting the lines so far, so don't try where no number starts
+			if !next.rest().starts_with(|c:char| c.is_ascii_digit() || c == '-') {
+				return Ok(numbers);
+			}
+			match next.number() {
+				Ok(n) if next.offset > self.offset => {
+					numbers.push(n);
+					*self = next;
+				}
+				_ => return Ok(numbers),
+			}
+		}
+	}
+
+	/// "<key>: <value>"
+	pub fn field<T>(&mut self, key:&'static str, value:impl FnOnce(&mut Self) -> Result<T>) -> Result<T> {
+		self.tag(key)?;
+		self.tag(":")?;
+		self.spaces();
+		value(self)
+	}
+
+	/// "<name> <number>:", returning the number
+	pub fn header<T:FromStr>(&mut self, name:&'static str) -> Result<T> {
+		self.tag(name)?;
+		self.tag(" ")?;
+		self.spaces();
+		let n = self.number()?;
+		self.tag(":")?;
+		self.spaces();
+		Ok(n)
+	}
+
+	// how long the line break here is, if there is one: CRLF too, for checkouts that convert line endings
+	fn line_break(&self) -> Option<usize> {
+		let rest = self.rest();
+		if rest.starts_with('\n') {
+			Some(1)
+		} else if rest.starts_with("\r\n") {
+			Some(2)
+		} else {
+			None
+		}
+	}
+
+	/// A line break, "\n" or "\r\n", or the end of the input
+	pub fn eol(&mut self) -> Result<()> {
+		if let Some(len) = self.line_break() {
+			self.advance(len);
+		} else if !self.is_empty() {
+			return Err(self.error(Expected::EndOfLine));
+		}
+		Ok(())
+	}
+
+	/// Lines, each parsed whole, up to a blank line or the end
+	pub fn lines<T>(&mut self, mut line:impl FnMut(&mut Self) -> Result<T>) -> Result<Vec<T>> {
+		let mut lines = vec![];
+		while !self.is_empty() && self.line_break().is_none() {
+			lines.push(line(self)?);
+			self.eol()?;
+		}
+		Ok(lines)
+	}
+
+	/// Blocks of lines, one or more, separated by blank lines
+	pub fn blocks<T>(&mut self, mut block:impl FnMut(&mut Self) -> Result<T>) -> Result<Vec<T>> {
+		let mut blocks = vec![block(self)?];
+		while self.line_break().is_some() {
+			self.take_while(|c| c == '\n' || c == '\r');
+			if self.is_empty() {
+				break;
+			}
+			blocks.push(block(self)?);
+		}
+		Ok(blocks)
+	}
+
+	/// Lines of cells up to a blank line or the end, all as wide as the first one
+	pub fn grid<T>(&mut self, mut cell:impl FnMut(char) -> Option<T>) -> Result<Grid<T>> {
+		let mut width = None;
+		let mut cells = vec![];
+		while !self.is_empty() && self.line_break().is_none() {
+			let start = self.clone();
+			let row = self.take_while(|c| c != '\n' && c != '\r');
+			for (i, c) in row.char_indices() {
+				let Some(value) = cell(c) else {
+					let mut at = start.clone();
+					at.advance(i);
+					return Err(at.error(Expected::Cell));
+				};
+				cells.push(value);
+			}
+			let w = *width.get_or_insert(row.chars().count());
+			if row.chars().count() != w {
+				return Err(start.error(Expected::Width(w)));
+			}
+			self.eol()?;
+		}
+		Ok(Grid::new(width.unwrap_or(0), cells))
+	}
+
+	/// Nothing left, but maybe blank lines
+	pub fn end(&mut self) -> Result<()> {
+		while let Some(len) = self.line_break() {
+			self.advance(len);
+		}
+		match self.is_empty() {
+			true => Ok(()),
+			false => Err(self.error(Expected::End)),
+		}
+	}
+}
+
+/// Parse a whole text
+pub fn all<'a, T>(text:&'a str, parser:impl FnOnce(&mut Cursor<'a>) -> Result<T>) -> Result<T> {
+	let mut cursor = Cursor::new(text);
+	let value = parser(&mut cursor)?;
+	cursor.end()?;
+	Ok(value)
+}
+
+#[cfg(test)]
+mod tests {
+	use pretty_assertions::{assert_eq, assert_str_eq};
+	use super::*;
+
+	#[test]
+	fn test_numbers() {
+		assert_eq!(all("-42", Cursor::number::<i64>), Ok(-42));
+		assert_eq!(all("Time:      7  15   30\n", |c| c.field("Time", Cursor::numbers::<u32>)), Ok(vec![7, 15, 30]));
+		assert_eq!(all("41 48 |", |c| { let n = c.numbers::<u8>()?; c.spaces(); c.tag("|")?; Ok(n) }), Ok(vec![41, 48]));
+		assert_eq!(all("1 -2 - 3", |c| { let n = c.numbers::<i8>()?; c.tag(" - 3")?; Ok(n) }), Ok(vec![1, -2]));
+
+		let error = all("1\n2 and 3\n", |c| c.lines(Cursor::number::<u8>)).unwrap_err();
+		assert_str_eq!(error.to_string(), "line 2, column 2: expected the end of the line, found \" and 3\"");
+		let error = all("300", Cursor::number::<u8>).unwrap_err();
+		assert_eq!((error.column, error.expected), (1, Expected::Number));
+	}
+
+	#[test]
+	fn test_structure() {
+		let cubes = |c:&mut Cursor| {
+			let n:u32 = c.number()?;
+			c.tag(" ")?;
+			Ok((n, c.one_of(&[("red", 'r'), ("green", 'g'), ("blue", 'b')])?))
+		};
+		let game = |c:&mut Cursor| Ok((c.header::<u32>("Game")?, c.list("; ", |c| c.list(", ", cubes))?));
+		assert_eq!(
+			all("Game 12: 3 blue, 4 red; 2 green\n", game),
+			Ok((12, vec![vec![(3, 'b'), (4, 'r')], vec![(2, 'g')]]))
+		);
+		let error = all("Game 1: 3 blue, 4 pink\n", game).unwrap_err();
+		assert_str_eq!(error.to_string(), "line 1, column 19: expected one of red, green, blue, found \"pink\"");
+
+		let blocks = all("a\nb\n\n\nc\n\n", |c| c.blocks(|c| c.lines(|c| c.identifier())));
+		assert_eq!(blocks, Ok(vec![vec!["a", "b"], vec!["c"]]));
+	}
+
+	#[test]
+	fn test_grid() {
+		let cell = |c| match c { '.' => Some(false), '#' => Some(true), _ => None };
+		let grid = all(".#.\n#..\n", |c| c.grid(cell)).unwrap();
+		assert_eq!((grid.width(), grid[(1,0)], grid[(1,1)]), (3, true, false));
+
+		let error = all(".#.\n#.\n", |c| c.grid(cell)).unwrap_err();
+		assert_eq!((error.line, error.column, error.expected), (2, 1, Expected::Width(3)));
+		let error = all(".#.\n#.?\n", |c| c.grid(cell)).unwrap_err();
+		assert_eq!((error.line, error.column, error.expected), (2, 3, Expected::Cell));
+	}
+
+	#[test]
+	fn test_crlf() {
+		let blocks = all("a\r\nb\r\n\r\nc\r\n\r\n", |c| c.blocks(|c| c.lines(|c| c.identifier())));
+		assert_eq!(blocks, Ok(vec![vec!["a", "b"], vec!["c"]]));
+		let grid = all(".#\r\n#.\r\n", |c| c.grid(|c| Some(c == '#'))).unwrap();
+		assert_eq!((grid.width(), grid[(1,0)], grid[(0,1)]), (2, true, true));
+
+		let error = all("1\r\n2 and 3\r\n", |c| c.lines(Cursor::number::<u8>)).unwrap_err();
+		assert_str_eq!(error.to_string(), "line 2, column 2: expected the end of the line, found \" and 3\"");
+	}
+}
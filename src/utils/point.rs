@@ -0,0 +1,162 @@
+// Signed 2D points and vectors, for moving around grids and beyond them
+
+use std::{fmt, ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign}};
+
+use super::grid::Pos;
+
+/// A point, or a vector between points, with y going down like in a grid
+#[derive(Debug,Clone,Copy,Default,PartialEq,Eq,PartialOrd,Ord,Hash)]
+pub struct Point {
+	pub x: i64,
+	pub y: i64,
+}
+
+pub type Vec2 = Point;
+
+impl Point {
+
+	pub const ORIGIN:Point = Point::new(0,0);
+	pub const UP:Vec2 = Point::new(0,-1);
+	pub const RIGHT:Vec2 = Point::new(1,0);
+	pub const DOWN:Vec2 = Point::new(0,1);
+	pub const LEFT:Vec2 = Point::new(-1,0);
+
+	/// Up, right, down then left
+	pub const DIRECTIONS4:[Vec2;4] = [Point::UP, Point::RIGHT, Point::DOWN, Point::LEFT];
+
+	/// Clockwise from the top left, diagonals included
+	pub const DIRECTIONS8:[Vec2;8] = [
+		Point::new(-1,-1), Point::UP, Point::new(1,-1), Point::RIGHT,
+		Point::new(1,1), Point::DOWN, Point::new(-1,1), Point::LEFT,
+	];
+
+	pub const fn new(x:i64, y:i64) -> Self {
+		Point { x, y }
+	}
+
+	/// Distance moving only along the axes
+	pub fn manhattan(self, other:Point) -> u64 {
+		self.x.abs_diff(other.x) + self.y.abs_diff(other.y)
+	}
+
+	/// Distance moving diagonals included, like a king in chess
+	pub fn chebyshev(self, other:Point) -> u64 {
+		self.x.abs_diff(other.x).max(self.y.abs_diff(other.y))
+	}
+
+	/// Turned a quarter clockwise around the origin, so that up becomes right
+	pub fn rotate_right(self) -> Self {
+		Point::new(-self.y, self.x)
+	}
+
+	/// Turned a quarter counterclockwise around the origin, so that up becomes left
+	pub fn rotate_left(self) -> Self {
+		Point::new(self.y, -self.x)
+	}
+
+	/// The point of a cell of a grid `width` wide, from the cell's index in its rows
+	pub fn from_index(index:usize, width:usize) -> Self {
+		Point::from((index % width, index / width))
+	}
+
+	/// The index of the cell at this point, in the rows of a grid `width` wide,
+	/// unless the point is off its sides
+	pub fn to_index(self, width:usize) -> Option<usize> {
+		let (x,y) = Pos::try_from(self).ok()?;
+		(x < width).then(|| y * width + x)
+	}
+}
+
+impl From<Pos> for Point {
+	fn from((x,y):Pos) -> Self {
+		Point::new(x.try_into().unwrap(), y.try_into().unwrap())
+	}
+}
+
+/// A position in a grid, unless the point is above it or left of it
+impl TryFrom<Point> for Pos {
+	type Error = std::num::TryFromIntError;
+	fn try_from(p:Point) -> Result<Self,Self::Error> {
+		Ok((p.x.try_into()?, p.y.try_into()?))
+	}
+}
+
+impl Add for Point {
+	type Output = Point;
+	fn add(self, v:Vec2) -> Point { Point::new(self.x + v.x, self.y + v.y) }
+}
+
+impl Sub for Point {
+	type Output = Vec2;
+	fn sub(self, p:Point) -> Vec2 { Point::new(self.x - p.x, self.y - p.y) }
+}
+
+impl Mul<i64> for Point {
+	type Output = Vec2;
+	fn mul(self, n:i64) -> Vec2 { Point::new(self.x * n, self.y * n) }
+}
+
+impl Neg for Point {
+	type Output = Vec2;
+	fn neg(self) -> Vec2 { Point::new(-self.x, -self.y) }
+}
+
+impl AddAssign for Point {
+	fn add_assign(&mut self, v:Vec2) { *self = *self + v; }
+}
+
+impl SubAssign for Point {
+	fn sub_assign(&mut self, v:Vec2) { *self = *self - v; }
+}
+
+impl fmt::Display for Point {
+	fn fmt(&self, f:&mut fmt::Formatter) -> fmt::Result {
+		write!(f, "({},{})", self.x, self.y)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use pretty_assertions::assert_eq;
+	use super::*;
+
+	#[test]
+	fn test_arithmetic() {
+		let mut p = Point::new(3,-2);
+		assert_eq!(p + Point::UP * 3, Point::new(3,-5));
+		assert_eq!(p - Point::new(5,5), Point::new(-2,-7));
+		assert_eq!(-p, Point::new(-3,2));
+		p += Point::LEFT;
+		p -= Point::DOWN;
+		assert_eq!(p, Point::new(2,-3));
+		assert_eq!(p.to_string(), "(2,-3)");
+	}
+
+	#[test]
+	fn test_distances() {
+		let (a, b) = (Point::new(-1,4), Point::new(3,1));
+		assert_eq!(a.manhattan(b), 7);
+		assert_eq!(a.chebyshev(b), 4);
+		assert!(Point::DIRECTIONS8.iter().all(|&d| Point::ORIGIN.chebyshev(d) == 1));
+	}
+
+	#[test]
+	fn test_rotate() {
+		assert_eq!(Point::DIRECTIONS4.map(Point::rotate_right), [Point::RIGHT, Point::DOWN, Point::LEFT, Point::UP]);
+		assert_eq!(Point::DIRECTIONS4.map(Point::rotate_left), [Point::LEFT, Point::UP, Point::RIGHT, Point::DOWN]);
+		let p = Point::new(5,-7);
+		assert_eq!(p.rotate_right().rotate_right(), -p);
+		assert_eq!(p.rotate_left().rotate_right(), p);
+	}
+
+	#[test]
+	fn test_grid_conversions() {
+		assert_eq!(Point::from((4,2)), Point::new(4,2));
+		assert_eq!(Pos::try_from(Point::new(4,2)), Ok((4,2)));
+		assert!(Pos::try_from(Point::new(-1,2)).is_err());
+		assert_eq!(Point::from_index(11, 5), Point::new(1,2));
+		assert_eq!(Point::new(1,2).to_index(5), Some(11));
+		assert_eq!(Point::new(5,2).to_index(5), None);
+		assert_eq!(Point::new(0,-1).to_index(5), None);
+	}
+}
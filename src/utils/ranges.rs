@@ -0,0 +1,238 @@
+// Sets of integers, kept as sorted half-open ranges
+
+use std::ops::{Add, Range, Sub};
+
+/// What a range set can hold: any of the integer types
+pub trait Int: Copy + Ord + Default + Add<Output = Self> + Sub<Output = Self> {}
+
+impl<T:Copy + Ord + Default + Add<Output = T> + Sub<Output = T>> Int for T {}
+
+/// A union of half-open ranges, normalized: sorted, none empty, and none touching another
+#[derive(Debug,Clone,PartialEq,Eq,Hash)]
+pub struct RangeSet<T> {
+	ranges: Vec<Range<T>>,
+}
+
+impl<T:Int> Default for RangeSet<T> {
+	fn default() -> Self {
+		RangeSet { ranges: vec![] }
+	}
+}
+
+impl<T:Int> RangeSet<T> {
+
+	pub fn new() -> Self {
+		RangeSet::default()
+	}
+
+	pub fn ranges(&self) -> &[Range<T>] {
+		&self.ranges
+	}
+
+	pub fn iter(&self) -> impl Iterator<Item = Range<T>> + '_ {
+		self.ranges.iter().cloned()
+	}
+
+	pub fn is_empty(&self) -> bool {
+		self.ranges.is_empty()
+	}
+
+	/// How many integers the set holds
+	pub fn len(&self) -> T {
+		self.ranges.iter().fold(T::default(), |len, r| len + (r.end - r.start))
+	}
+
+	pub fn min(&self) -> Option<T> {
+		self.ranges.first().map(|r| r.start)
+	}
+
+	pub fn contains(&self, n:T) -> bool {
+		let i = self.ranges.partition_point(|r| r.end <= n);
+		self.ranges.get(i).is_some_and(|r| r.start <= n)
+	}
+
+	pub fn insert(&mut self, range:Range<T>) {
+		*self = self.iter().chain([range]).collect();
+	}
+
+	pub fn union(&self, other:&Self) -> Self {
+		self.iter().chain(other.iter()).collect()
+	}
+
+	pub fn intersection(&self, other:&Self) -> Self {
+		let (a, b) = (&self.ranges, &other.ranges);
+		let (mut i, mut j) = (0, 0);
+		let mut ranges = vec![];
+		while i < a.len() && j < b.len() {
+			let start = a[i].start.max(b[j].start);
+			let end = a[i].end.min(b[j].end);
+			if start < end {
+				ranges.push(start..end);
+			}
+			// move past whichever ends first
+			if a[i].end < b[j].end { i += 1 } else { j += 1 }
+		}
+		// what's left between gaps of both sets can't touch
+		RangeSet { ranges }
+	}
+
+	pub fn difference(&self, other:&Self) -> Self {
+		let cuts = &other.ranges;
+		let mut ranges = vec![];
+		let mut first = 0;
+		for r in &self.ranges {
+			// the ranges ending before this one end before the next ones too
+			while first < cuts.len() && cuts[first].end <= r.start {
+				first += 1;
+			}
+			let mut start = r.start;
+			for cut in cuts[first..].iter().take_while(|cut| cut.start < r.end) {
+				if start < cut.start {
+					ranges.push(start..cut.start);
+				}
+				start = start.max(cut.end);
+			}
+			if start < r.end {
+				ranges.push(start..r.end);
+			}
+		}
+		RangeSet { ranges }
+	}
+
+	/// Every integer plus `n`
+	pub fn shift_up(&self, n:T) -> Self {
+		RangeSet { ranges: self.iter().map(|r| r.start + n..r.end + n).collect() }
+	}
+
+	/// Every integer minus `n`
+	pub fn shift_down(&self, n:T) -> Self {
+		RangeSet { ranges: self.iter().map(|r| r.start - n..r.end - n).collect() }
+	}
+}
+
+impl<T:Int> FromIterator<Range<T>> for RangeSet<T> {
+	fn from_iter<I:IntoIterator<Item = Range<T>>>(iter:I) -> Self {
+		let mut sorted:Vec<Range<T>> = iter.into_iter().filter(|r| r.start < r.end).collect();
+		sorted.sort_by_key(|r| r.start);
+		let mut ranges:Vec<Range<T>> = Vec::with_capacity(sorted.len());
+		for r in sorted {
+			match ranges.last_mut() {
+				Some(last) if r.start <= last.end => last.end = last.end.max(r.end),
+				_ => ranges.push(r),
+			}
+		}
+		RangeSet { ranges }
+	}
+}
+
+impl<T:Int> From<Range<T>> for RangeSet<T> {
+	fn from(range:Range<T>) -> Self {
+		[range].into_iter().collect()
+	}
+}
+
+/// Cut a range wherever the `cuts` start or end. Each piece comes with the index of
+/// the first cut it is in, if any, like a value is mapped by the first mapper that has it.
+pub fn split<T:Int>(range:Range<T>, cuts:&[Range<T>]) -> Vec<(Range<T>, Option<usize>)> {
+	let mut bounds:Vec<T> = cuts.iter()
+		.flat_map(|cut| [cut.start, cut.end])
+		.filter(|&n| range.start < n && n < range.end)
+		.chain([range.start, range.end])
+		.collect();
+	bounds.sort();
+	bounds.dedup();
+	bounds.windows(2)
+		.map(|w| (w[0]..w[1], cuts.iter().position(|cut| cut.contains(&w[0]))))
+		.collect()
+}
+
+#[cfg(test)]
+mod tests {
+	use std::collections::BTreeSet;
+	use pretty_assertions::assert_eq;
+	use proptest::prelude::*;
+	use super::*;
+
+	// a brute force model of the set: every integer in it
+	fn model(set:&RangeSet<u8>) -> BTreeSet<u8> {
+		set.iter().flatten().collect()
+	}
+
+	fn ranges() -> impl Strategy<Value = Vec<Range<u8>>> {
+		prop::collection::vec((0u8..100, 0u8..20).prop_map(|(start, len)| start..start + len), 0..8)
+	}
+
+	fn normalized(set:&RangeSet<u8>) -> bool {
+		set.ranges().iter().all(|r| r.start < r.end) && set.ranges().windows(2).all(|w| w[0].end < w[1].start)
+	}
+
+	#[test]
+	fn test_set() {
+		let set:RangeSet<i64> = [5..8, -3..0, 0..2, 7..10, 12..12].into_iter().collect();
+		assert_eq!(set.ranges(), [-3..2, 5..10]);
+		assert_eq!(set.len(), 10);
+		assert_eq!(set.min(), Some(-3));
+		assert!(set.contains(-3) && set.contains(9) && !set.contains(2) && !set.contains(10));
+		assert_eq!(set.difference(&(0..6).into()).ranges(), [-3..0, 6..10]);
+		assert_eq!(set.shift_down(3).ranges(), [-6..-1, 2..7]);
+	}
+
+	#[test]
+	fn test_split() {
+		let cuts = [98..100, 50..98];
+		assert_eq!(split(40..99, &cuts), [(40..50, None), (50..98, Some(1)), (98..99, Some(0))]);
+		assert_eq!(split(10..20, &cuts), [(10..20, None)]);
+		assert_eq!(split(10..10, &cuts), []);
+	}
+
+	proptest! {
+
+		#[test]
+		fn prop_normalized(a in ranges()) {
+			let set:RangeSet<u8> = a.iter().cloned().collect();
+			prop_assert!(normalized(&set));
+			prop_assert_eq!(model(&set), a.into_iter().flatten().collect::<BTreeSet<u8>>());
+			prop_assert_eq!(usize::from(set.len()), model(&set).len());
+		}
+
+		#[test]
+		fn prop_operations(a in ranges(), b in ranges(), n in 0u8..100) {
+			let (a, b):(RangeSet<u8>, RangeSet<u8>) = (a.into_iter().collect(), b.into_iter().collect());
+			let (ma, mb) = (model(&a), model(&b));
+
+			let union = a.union(&b);
+			let intersection = a.intersection(&b);
+			let difference = a.difference(&b);
+			for set in [&union, &intersection, &difference] {
+				prop_assert!(normalized(set));
+			}
+			prop_assert_eq!(model(&union), &ma | &mb);
+			prop_assert_eq!(model(&intersection), &ma & &mb);
+			prop_assert_eq!(model(&difference), &ma - &mb);
+
+			let mut inserted = a.clone();
+			b.iter().for_each(|r| inserted.insert(r));
+			prop_assert_eq!(&inserted, &union);
+
+			for i in 0..120 {
+				prop_assert_eq!(a.contains(i), ma.contains(&i));
+			}
+			prop_assert_eq!(model(&a.shift_up(n)), ma.iter().map(|i| i + n).collect::<BTreeSet<u8>>());
+			prop_assert_eq!(a.shift_up(n).shift_down(n), a);
+		}
+
+		#[test]
+		fn prop_split(start in 0u8..100, len in 0u8..50, cuts in ranges()) {
+			let range = start..start + len;
+			let pieces = split(range.clone(), &cuts);
+			// the pieces cover the range, in order
+			prop_assert_eq!(pieces.iter().flat_map(|(p, _)| p.clone()).collect::<Vec<u8>>(), range.collect::<Vec<u8>>());
+			// and every integer of a piece is in the same first cut
+			for (piece, cut) in pieces {
+				for i in piece {
+					prop_assert_eq!(cuts.iter().position(|c| c.contains(&i)), cut);
+				}
+			}
+		}
+	}
+}
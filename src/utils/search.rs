@@ -0,0 +1,224 @@
+// Shortest paths through states that are only known by where they lead
+
+use std::{cmp::Reverse, collections::{BinaryHeap, HashMap, VecDeque}, hash::Hash};
+
+/// A way to a goal: what it cost, and the states along it, from a start to the goal
+#[derive(Debug,Clone,PartialEq,Eq)]
+pub struct Path<S> {
+	pub cost: u64,
+	pub states: Vec<S>,
+}
+
+// the states seen so far, each with the one it was reached from
+struct Visited<S> {
+	states: Vec<(S,Option<usize>)>,
+	ids: HashMap<S,usize>,
+}
+
+impl<S:Clone + Eq + Hash> Visited<S> {
+
+	fn new() -> Self {
+		Visited { states: vec![], ids: HashMap::new() }
+	}
+
+	// the state's id, and whether it was seen for the first time
+	fn visit(&mut self, state:S, from:Option<usize>) -> (usize,bool) {
+		if let Some(&id) = self.ids.get(&state) {
+			return (id, false);
+		}
+		let id = self.states.len();
+		self.states.push((state.clone(), from));
+		self.ids.insert(state, id);
+		(id, true)
+	}
+
+	fn path(&self, mut id:usize, cost:u64) -> Path<S> {
+		let mut states = vec![self.states[id].0.clone()];
+		while let Some(from) = self.states[id].1 {
+			states.push(self.states[from].0.clone());
+			id = from;
+		}
+		states.reverse();
+		Path { cost, states }
+	}
+}
+
+/// The fewest steps from any of the starts to a goal, when every step costs 1
+pub fn bfs<S,I>(
+	starts: impl IntoIterator<Item = S>,
+	mut successors: impl FnMut(&S) -> I,
+	mut goal: impl FnMut(&S) -> bool,
+) -> Option<Path<S>>
+where
+	S: Clone + Eq + Hash,
+	I: IntoIterator<Item = S>,
+{
+	let mut visited = Visited::new();
+	let mut queue:VecDeque<(usize,u64)> = starts.into_iter()
+		.filter_map(|s| match visited.visit(s, None) { (id, true) => Some((id, 0)), _ => None })
+		.collect();
+
+	while let Some((id, steps)) = queue.pop_front() {
+		let state = visited.states[id].0.clone();
+		if goal(&state) {
+			return Some(visited.path(id, steps));
+		}
+		for next in successors(&state) {
+			if let (next, true) = visited.visit(next, Some(id)) {
+				queue.push_back((next, steps + 1));
+			}
+		}
+	}
+	None
+}
+
+/// The cheapest way from any of the starts to a goal, with Dijkstra's algorithm
+pub fn dijkstra<S,I>(
+	starts: impl IntoIterator<Item = S>,
+	successors: impl FnMut(&S) -> I,
+	goal: impl FnMut(&S) -> bool,
+) -> Option<Path<S>>
+where
+	S: Clone + Eq + Hash,
+	I: IntoIterator<Item = (S,u64)>,
+{
+	astar(starts, successors, goal, |_| 0)
+}
+
+/// The cheapest way from any of the starts to a goal, with A*.
+/// The heuristic must never overestimate what's left to pay, or the path found may not be the cheapest.
+/// It needn't be consistent: a state is opened again when a cheaper way to it turns up.
+pub fn astar<S,I>(
+	starts: impl IntoIterator<Item = S>,
+	mut successors: impl FnMut(&S) -> I,
+	mut goal: impl FnMut(&S) -> bool,
+	mut heuristic: impl FnMut(&S) -> u64,
+) -> Option<Path<S>>
+where
+	S: Clone + Eq + Hash,
+	I: IntoIterator<Item = (S,u64)>,
+{
+	let mut visited = Visited::new();
+	// the cheapest cost found to every state, and whether it was expanded with it
+	let mut costs:Vec<(u64,bool)> = vec![];
+	// (estimated total, cost so far, state)
+	let mut heap = BinaryHeap::new();
+
+	for start in starts {
+		if let (id, true) = visited.visit(start, None) {
+			costs.push((0, false));
+			heap.push(Reverse((heuristic(&visited.states[id].0), 0, id)));
+		}
+	}
+
+	while let Some(Reverse((_, cost, id))) = heap.pop() {
+		if costs[id].1 || cost > costs[id].0 {
+			continue;
+		}
+		costs[id].1 = true;
+		let state = visited.states[id].0.clone();
+		if goal(&state) {
+			return Some(visited.path(id, cost));
+		}
+		for (next, step) in successors(&state) {
+			let total = cost + step;
+			let (next, new) = visited.visit(next, Some(id));
+			if new {
+				costs.push((total, false));
+			} else if total >= costs[next].0 {
+				continue;
+			} else {
+				costs[next] = (total, false);
+				visited.states[next].1 = Some(id);
+			}
+			heap.push(Reverse((total + heuristic(&visited.states[next].0), total, next)));
+		}
+	}
+	None
+}
+
+#[cfg(test)]
+mod tests {
+	use pretty_assertions::assert_eq;
+	use crate::utils::{grid::Grid, point::Point};
+	use super::*;
+
+	// walls are '#', every other cell costs its digit to step on
+	const MAZE:&str = "\
+1163751
+1381#73
+2136511
+3694#31
+7463417
+1319128
+1359912
+";
+
+	fn maze() -> Grid<char> {
+		Grid::parse(MAZE, |c| c)
+	}
+
+	#[test]
+	fn test_bfs() {
+		let grid = maze();
+		let end = Point::new(6, 6);
+		let steps = |p:&Point| Point::DIRECTIONS4.map(|d| *p + d).into_iter()
+			.filter(|&n| grid.at(n).is_some_and(|&c| c != '#'))
+			.collect::<Vec<_>>();
+
+		let path = bfs([Point::ORIGIN], steps, |&p| p == end).unwrap();
+		assert_eq!(path.cost, 12);
+		assert_eq!(path.states.len(), 13);
+		assert_eq!((path.states[0], path.states[12]), (Point::ORIGIN, end));
+		assert!(path.states.windows(2).all(|w| w[0].manhattan(w[1]) == 1));
+
+		// the nearest of several starts
+		let path = bfs([Point::ORIGIN, Point::new(6, 0)], steps, |&p| p == end).unwrap();
+		assert_eq!(path.cost, 6);
+		assert_eq!(path.states[0], Point::new(6, 0));
+
+		assert_eq!(bfs([Point::ORIGIN], steps, |&p| p == Point::new(4, 1)), None);
+	}
+
+	#[test]
+	fn test_dijkstra_astar() {
+		let grid = maze();
+		let end = Point::new(6, 6);
+		let steps = |p:&Point| Point::DIRECTIONS4.map(|d| *p + d).into_iter()
+			.filter_map(|n| grid.at(n).and_then(|c| c.to_digit(10)).map(|c| (n, u64::from(c))))
+			.collect::<Vec<_>>();
+
+		let path = dijkstra([Point::ORIGIN], steps, |&p| p == end).unwrap();
+		let paid:u64 = path.states[1..].iter().map(|&p| u64::from(grid.at(p).unwrap().to_digit(10).unwrap())).sum();
+		assert_eq!(path.cost, paid);
+		assert_eq!(path.cost, 28);
+
+		// the same cost, guided by the distance left
+		let guided = astar([Point::ORIGIN], steps, |&p| p == end, |p| p.manhattan(end)).unwrap();
+		assert_eq!(guided.cost, path.cost);
+	}
+
+	#[test]
+	fn test_inconsistent() {
+		// the heuristic never overestimates, but drops by more than a step costs from a to b,
+		// so b is first expanded by the dearer way from s
+		let edges = [('s','a',1), ('s','b',3), ('a','b',1), ('b','g',3)];
+		let steps = |&n:&char| edges.iter().filter(move |e| e.0 == n).map(|e| (e.1, e.2));
+		let heuristic = |&n:&char| if n == 'a' { 3 } else { 0 };
+
+		let path = astar(['s'], steps, |&n| n == 'g', heuristic).unwrap();
+		assert_eq!(path, Path { cost: 5, states: vec!['s','a','b','g'] });
+	}
+
+	#[test]
+	fn test_implicit() {
+		// measuring 4 litres with a 3 and a 5 litre jug, states of how much each one holds
+		let steps = |&(a, b):&(u8,u8)| {
+			let (ab, ba) = (a.min(5 - b), b.min(3 - a));
+			[(3, b), (a, 5), (0, b), (a, 0), (a - ab, b + ab), (a + ba, b - ba)]
+		};
+		let path = bfs([(0, 0)], steps, |&(_, b)| b == 4).unwrap();
+		assert_eq!(path.cost, 6);
+		assert_eq!(path.states, [(0,0), (0,5), (3,2), (0,2), (2,0), (2,5), (3,4)]);
+	}
+}
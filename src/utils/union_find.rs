@@ -0,0 +1,241 @@
+// Disjoint sets: which things have been joined together, directly or not
+
+use std::{collections::HashMap, hash::Hash};
+
+/// Sets of the numbers 0..len, joined with path compression and union by rank
+#[derive(Debug,Clone,Default)]
+pub struct UnionFind {
+	parent: Vec<usize>,
+	rank: Vec<u8>,
+	size: Vec<usize>,
+	count: usize,
+}
+
+impl UnionFind {
+
+	/// Every number in a set of its own
+	pub fn new(len:usize) -> Self {
+		UnionFind { parent: (0..len).collect(), rank: vec![0; len], size: vec![1; len], count: len }
+	}
+
+	/// A new number, in a set of its own
+	pub fn add(&mut self) -> usize {
+		let n = self.parent.len();
+		self.parent.push(n);
+		self.rank.push(0);
+		self.size.push(1);
+		self.count += 1;
+		n
+	}
+
+	pub fn len(&self) -> usize {
+		self.parent.len()
+	}
+
+	pub fn is_empty(&self) -> bool {
+		self.parent.is_empty()
+	}
+
+	/// How many sets there are
+	pub fn count(&self) -> usize {
+		self.count
+	}
+
+	/// The number standing for the set `n` is in
+	pub fn find(&mut self, n:usize) -> usize {
+		let mut root = n;
+		while self.parent[root] != root {
+			root = self.parent[root];
+		}
+		// everything on the way now points straight at the root
+		let mut n = n;
+		while self.parent[n] != root {
+			n = std::mem::replace(&mut self.parent[n], root);
+		}
+		root
+	}
+
+	/// Join the sets of a and b, false if they were already the same
+	pub fn union(&mut self, a:usize, b:usize) -> bool {
+		let (a, b) = (self.find(a), self.find(b));
+		if a == b {
+			return false;
+		}
+		// the shallower tree goes under the deeper one
+		let (root, child) = if self.rank[a] < self.rank[b] { (b, a) } else { (a, b) };
+		self.parent[child] = root;
+		self.size[root] += self.size[child];
+		if self.rank[a] == self.rank[b] {
+			self.rank[root] += 1;
+		}
+		self.count -= 1;
+		true
+	}
+
+	pub fn connected(&mut self, a:usize, b:usize) -> bool {
+		self.find(a) == self.find(b)
+	}
+
+	/// How many numbers are in the set `n` is in
+	pub fn size(&mut self, n:usize) -> usize {
+		let root = self.find(n);
+		self.size[root]
+	}
+
+	/// The numbers in every set, smallest first, the sets by their smallest number
+	pub fn components(&mut self) -> Vec<Vec<usize>> {
+		let mut indices:Vec<Option<usize>> = vec![None; self.len()];
+		let mut components:Vec<Vec<usize>> = vec![];
+		for n in 0..self.len() {
+			let root = self.find(n);
+			let i = *indices[root].get_or_insert_with(|| {
+				components.push(vec![]);
+				components.len() - 1
+			});
+			components[i].push(n);
+		}
+		components
+	}
+}
+
+/// Sets of anything hashable, numbered as they are first seen
+#[derive(Debug,Clone)]
+pub struct KeyedUnionFind<K> {
+	ids: HashMap<K,usize>,
+	keys: Vec<K>,
+	sets: UnionFind,
+}
+
+impl<K> Default for KeyedUnionFind<K> {
+	fn default() -> Self {
+		KeyedUnionFind { ids: HashMap::new(), keys: vec![], sets: UnionFind::default() }
+	}
+}
+
+impl<K:Clone + Eq + Hash> KeyedUnionFind<K> {
+
+	pub fn new() -> Self {
+		KeyedUnionFind::default()
+	}
+
+	/// The key's number, added in a set of its own if it's new
+	pub fn insert(&mut self, key:K) -> usize {
+		if let Some(&id) = self.ids.get(&key) {
+			return id;
+		}
+		let id = self.sets.add();
+		self.keys.push(key.clone());
+		self.ids.insert(key, id);
+		id
+	}
+
+	pub fn len(&self) -> usize {
+		self.keys.len()
+	}
+
+	pub fn is_empty(&self) -> bool {
+		self.keys.is_empty()
+	}
+
+	pub fn count(&self) -> usize {
+		self.sets.count()
+	}
+
+	/// The key standing for the set `key` is in, if it was ever inserted
+	pub fn find(&mut self, key:&K) -> Option<&K> {
+		let id = *self.ids.get(key)?;
+		let root = self.sets.find(id);
+		Some(&self.keys[root])
+	}
+
+	/// Join the sets of a and b, inserting them if needed, false if they were already the same
+	pub fn union(&mut self, a:K, b:K) -> bool {
+		let (a, b) = (self.insert(a), self.insert(b));
+		self.sets.union(a, b)
+	}
+
+	pub fn connected(&mut self, a:&K, b:&K) -> bool {
+		match (self.ids.get(a), self.ids.get(b)) {
+			(Some(&a), Some(&b)) => self.sets.connected(a, b),
+			_ => a == b,
+		}
+	}
+
+	/// How many keys are in the set `key` is in, 0 if it was never inserted
+	pub fn size(&mut self, key:&K) -> usize {
+		self.ids.get(key).map_or(0, |&id| self.sets.size(id))
+	}
+
+	/// The keys in every set, in the order they were inserted
+	pub fn components(&mut self) -> Vec<Vec<K>> {
+		self.sets.components()
+			.into_iter()
+			.map(|ids| ids.into_iter().map(|id| self.keys[id].clone()).collect())
+			.collect()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use pretty_assertions::assert_eq;
+	use super::*;
+
+	#[test]
+	fn test_union_find() {
+		let mut sets = UnionFind::new(8);
+		assert_eq!(sets.count(), 8);
+		assert!(sets.union(0, 1));
+		assert!(sets.union(2, 3));
+		assert!(sets.union(1, 3));
+		assert!(!sets.union(0, 2));
+		assert!(sets.union(5, 7));
+		assert_eq!(sets.count(), 4);
+		assert!(sets.connected(0, 3));
+		assert!(!sets.connected(0, 5));
+		assert_eq!(sets.size(2), 4);
+		assert_eq!(sets.size(4), 1);
+		assert_eq!(sets.components(), [vec![0,1,2,3], vec![4], vec![5,7], vec![6]]);
+
+		let n = sets.add();
+		sets.union(n, 4);
+		assert_eq!(sets.components(), [vec![0,1,2,3], vec![4,8], vec![5,7], vec![6]]);
+	}
+
+	#[test]
+	fn test_against_labels() {
+		// joining along a chain, compared with relabelling everything on every join
+		let n = 200;
+		let mut sets = UnionFind::new(n);
+		let mut labels:Vec<usize> = (0..n).collect();
+		for i in 0..n {
+			let (a, b) = (i * 7 % n, i * 13 % n / 2);
+			let (la, lb) = (labels[a], labels[b]);
+			assert_eq!(sets.union(a, b), la != lb);
+			labels.iter_mut().filter(|l| **l == lb).for_each(|l| *l = la);
+			for x in (0..n).step_by(17) {
+				assert_eq!(sets.connected(a, x), labels[a] == labels[x]);
+				assert_eq!(sets.size(x), labels.iter().filter(|&&l| l == labels[x]).count());
+			}
+		}
+	}
+
+	#[test]
+	fn test_keyed() {
+		let mut sets = KeyedUnionFind::new();
+		sets.union("a", "b");
+		sets.union("c", "d");
+		sets.insert("e");
+		sets.union("d", "a");
+		assert_eq!(sets.len(), 5);
+		assert_eq!(sets.count(), 2);
+		assert!(sets.connected(&"b", &"c"));
+		assert!(!sets.connected(&"a", &"e"));
+		assert!(!sets.connected(&"a", &"z"));
+		assert_eq!(sets.size(&"c"), 4);
+		assert_eq!(sets.size(&"z"), 0);
+		assert_eq!(sets.find(&"z"), None);
+		let root = sets.find(&"d").copied();
+		assert_eq!(sets.find(&"b").copied(), root);
+		assert_eq!(sets.components(), [vec!["a","b","c","d"], vec!["e"]]);
+	}
+}
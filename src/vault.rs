@@ -0,0 +1,159 @@
+// Puzzle inputs and the ledger, encrypted so they can be kept in the repository.
+// An encrypted file sits next to the plain one, with an ".enc" suffix, and is only
+// decrypted in memory, when the plain one is missing.
+
+use std::{env, fs, io, path::{Path, PathBuf}};
+use chacha20poly1305::{aead::{Aead, AeadCore, KeyInit, OsRng}, XChaCha20Poly1305, XNonce};
+
+use crate::embedded;
+
+pub const KEY_VAR:&str = "AOC_KEY";
+pub const KEY_FILE:&str = ".aoc-key";
+
+const NONCE_LEN:usize = 24;
+
+/// A symmetric key, written as 64 hex digits
+#[derive(Clone)]
+pub struct Key([u8;32]);
+
+impl Key {
+
+	pub fn generate() -> Self {
+		Key(XChaCha20Poly1305::generate_key(&mut OsRng).into())
+	}
+
+	pub fn parse(hex:&str) -> io::Result<Self> {
+		let invalid = || io::Error::new(io::ErrorKind::InvalidData, "the key should be 64 hex digits");
+		let hex = hex.trim();
+		if hex.len() != 64 || !hex.is_ascii() {
+			return Err(invalid());
+		}
+		let mut key = [0;32];
+		for (i, byte) in key.iter_mut().enumerate() {
+			*byte = u8::from_str_radix(&hex[2*i..2*i+2], 16).map_err(|_| invalid())?;
+		}
+		Ok(Key(key))
+	}
+
+	pub fn to_hex(&self) -> String {
+		self.0.iter().map(|b| format!("{b:02x}")).collect()
+	}
+
+	/// The key from the environment, or else from the key file
+	pub fn load() -> io::Result<Option<Self>> {
+		match env::var(KEY_VAR) {
+			Ok(hex) => Key::parse(&hex).map(Some),
+			Err(_) => match fs::read_to_string(KEY_FILE) {
+				Ok(hex) => Key::parse(&hex).map(Some),
+				Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+				Err(e) => Err(e),
+			}
+		}
+	}
+
+	pub fn encrypt(&self, plain:&[u8]) -> Vec<u8> {
+		let cipher = XChaCha20Poly1305::new(&self.0.into());
+		let nonce = XChaCha20Poly1305::generate_nonce(&mut OsRng);
+		let encrypted = cipher.encrypt(&nonce, plain).unwrap();
+		[nonce.as_slice(), &encrypted].concat()
+	}
+
+	pub fn decrypt(&self, data:&[u8]) -> io::Result<Vec<u8>> {
+		let invalid = || io::Error::new(io::ErrorKind::InvalidData, "can't decrypt, the key is wrong or the file is corrupt");
+		if data.len() < NONCE_LEN {
+			return Err(invalid());
+		}
+		let (nonce, encrypted) = data.split_at(NONCE_LEN);
+		let cipher = XChaCha20Poly1305::new(&self.0.into());
+		cipher.decrypt(XNonce::from_slice(nonce), encrypted).map_err(|_| invalid())
+	}
+}
+
+pub fn encrypted_path(path:&Path) -> PathBuf {
+	let mut name = path.as_os_str().to_owned();
+	name.push(".enc");
+	PathBuf::from(name)
+}
+
+/// A file from disk, or else from those built into the binary
+fn load(path:&Path) -> io::Result<Vec<u8>> {
+	match fs::read(path) {
+		Err(e) if e.kind() == io::ErrorKind::NotFound => embedded::get(path).map(<[u8]>::to_vec).ok_or(e),
+		result => result,
+	}
+}
+
+fn exists(path:&Path) -> bool {
+	path.exists() || embedded::get(path).is_some()
+}
+
+/// Read a file, or else decrypt its encrypted version with the key, if there is one
+pub fn read(path:&Path, key:Option<&Key>) -> io::Result<String> {
+	let data = match load(path) {
+		Err(e) if e.kind() == io::ErrorKind::NotFound => {
+			let (Some(key), Ok(data)) = (key, load(&encrypted_path(path))) else {
+				return Err(e);
+			};
+			key.decrypt(&data)?
+		}
+		result => result?,
+	};
+	String::from_utf8(data).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
+
+/// Read a file, or else decrypt its encrypted version with the loaded key
+pub fn read_to_string(path:&Path) -> io::Result<String> {
+	let key = if !exists(path) && exists(&encrypted_path(path)) { Key::load()? } else { None };
+	read(path, key.as_ref())
+}
+
+/// Encrypt a file next to it. Returns whether that changed anything, since an
+/// encrypted file that already holds the same contents is left alone.
+pub fn seal(path:&Path, key:&Key) -> io::Result<bool> {
+	let plain = fs::read(path)?;
+	let sealed = encrypted_path(path);
+	if let Ok(data) = fs::read(&sealed) {
+		if key.decrypt(&data).is_ok_and(|old| old == plain) {
+			return Ok(false);
+		}
+	}
+	fs::write(sealed, key.encrypt(&plain))?;
+	Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+	use pretty_assertions::assert_eq;
+	use super::*;
+
+	#[test]
+	fn test_key() {
+		let key = Key::generate();
+		assert_eq!(Key::parse(&key.to_hex()).unwrap().0, key.0);
+		assert!(Key::parse("0123").is_err());
+		assert!(Key::parse(&"g".repeat(64)).is_err());
+
+		let sealed = key.encrypt(b"seeds: 79 14\n");
+		assert_eq!(key.decrypt(&sealed).unwrap(), b"seeds: 79 14\n");
+		assert!(Key::generate().decrypt(&sealed).is_err());
+	}
+
+	#[test]
+	fn test_seal_and_read() {
+		let dir = env::temp_dir().join(format!("aoc-vault-{}", std::process::id()));
+		fs::create_dir_all(&dir).unwrap();
+		let path = dir.join("5.txt");
+		let key = Key::generate();
+
+		fs::write(&path, "seeds: 79 14\n").unwrap();
+		assert!(seal(&path, &key).unwrap());
+		assert!(!seal(&path, &key).unwrap());
+
+		fs::remove_file(&path).unwrap();
+		assert_eq!(read(&path, Some(&key)).unwrap(), "seeds: 79 14\n");
+		assert_eq!(read(&path, None).unwrap_err().kind(), io::ErrorKind::NotFound);
+		assert_eq!(read(&path, Some(&Key::generate())).unwrap_err().kind(), io::ErrorKind::InvalidData);
+
+		fs::remove_dir_all(dir).unwrap();
+	}
+}